@@ -28,6 +28,10 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        if context_attributes.protected_content {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let share_context = match context_attributes.shared_context.as_ref() {
             Some(RawContext::Cgl(share_context)) => unsafe {
                 share_context.cast::<NSOpenGLContext>().as_ref()
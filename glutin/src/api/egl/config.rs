@@ -1,14 +1,15 @@
 //! Everything related to finding and manipulating the `EGLConfig`.
 #![allow(clippy::unnecessary_cast)] // needed for 32bit & 64bit support
 
-use std::ops::Deref;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, RangeInclusive};
 use std::sync::Arc;
 use std::{fmt, mem};
 
 use raw_window_handle::RawWindowHandle;
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLConfig, EGLint};
+use glutin_egl_sys::egl::types::{EGLConfig, EGLDisplay, EGLenum, EGLint};
 
 use crate::config::{
     Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, RawConfig,
@@ -105,6 +106,17 @@ impl Display {
             }
         }
 
+        // Add transparent color.
+        if template.transparent_color {
+            config_attributes.push(egl::TRANSPARENT_TYPE as EGLint);
+            config_attributes.push(egl::TRANSPARENT_RGB as EGLint);
+        }
+
+        if template.native_renderable {
+            config_attributes.push(egl::NATIVE_RENDERABLE as EGLint);
+            config_attributes.push(egl::TRUE as EGLint);
+        }
+
         // Add minimum swap interval.
         if let Some(min_swap_interval) = template.min_swap_interval {
             config_attributes.push(egl::MIN_SWAP_INTERVAL as EGLint);
@@ -163,6 +175,10 @@ impl Display {
         // Push `egl::NONE` to terminate the list.
         config_attributes.push(egl::NONE as EGLint);
 
+        let prefer_conformant = template.prefer_conformant;
+        let requested_api = template.api.unwrap_or(Api::GLES2);
+
+        let _guard = self.inner.lock();
         let mut configs_number = self.configs_number() as EGLint;
         let mut found_configs: Vec<EGLConfig> =
             unsafe { vec![mem::zeroed(); configs_number as usize] };
@@ -207,11 +223,58 @@ impl Display {
             })
             .filter(move |config| {
                 !template.transparency || config.supports_transparency().unwrap_or(true)
+            })
+            .filter(move |config| {
+                // `eglChooseConfig` treats the sizes as the minimum.
+                !template.exact_color_sizes
+                    || (config.color_buffer_type() == Some(template.color_buffer_type)
+                        && config.alpha_size() == template.alpha_size)
             });
 
+        if prefer_conformant {
+            // The sort is stable, so the order of the platform is preserved otherwise.
+            let mut configs = configs.collect::<Vec<_>>();
+            configs.sort_by_key(|config| !config.conformant().contains(requested_api));
+            return Ok(Box::new(configs.into_iter()));
+        }
+
         Ok(Box::new(configs))
     }
 
+    /// Find the config with the given `EGL_CONFIG_ID`, like the one
+    /// persisted with [`Config::config_id`] to reselect the same config
+    /// across runs.
+    ///
+    /// [`ErrorKind::NotFound`] is returned when there's no such config, for
+    /// example after the driver update.
+    pub fn config_by_id(&self, id: u32) -> Result<Config> {
+        let config_attributes = [egl::CONFIG_ID as EGLint, id as EGLint, egl::NONE as EGLint];
+
+        let mut configs_number = 0;
+        let mut raw = unsafe { mem::zeroed() };
+        let _guard = self.inner.lock();
+        unsafe {
+            let result = self.inner.egl.ChooseConfig(
+                *self.inner.raw,
+                config_attributes.as_ptr(),
+                &mut raw,
+                1,
+                &mut configs_number,
+            );
+
+            if result == egl::FALSE {
+                return Err(ErrorKind::BadConfig.into());
+            }
+        }
+
+        if configs_number == 0 {
+            return Err(ErrorKind::NotFound.into());
+        }
+
+        let inner = Arc::new(ConfigInner { display: self.clone(), raw: EglConfig(raw) });
+        Ok(Config { inner })
+    }
+
     fn configs_number(&self) -> usize {
         unsafe {
             let mut num_configs = 0;
@@ -223,7 +286,10 @@ impl Display {
 
 /// A simple wrapper around `EGLConfig` that could be used with `EGLContext`
 /// and `EGLSurface`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The configs are compared by their `EGL_CONFIG_ID` on the same display,
+/// since the drivers may hand out distinct handles for the same config.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Config {
     pub(crate) inner: Arc<ConfigInner>,
 }
@@ -237,10 +303,161 @@ impl Config {
         unsafe { self.raw_attribute(egl::NATIVE_VISUAL_ID as EGLint) as u32 }
     }
 
+    /// The native visual type.
+    ///
+    /// The interpretation of this value is platform dependant, `None` is
+    /// returned when the config has no associated native visual type.
+    pub fn native_visual_type(&self) -> Option<i32> {
+        let ty = unsafe { self.raw_attribute(egl::NATIVE_VISUAL_TYPE as EGLint) };
+        (ty != egl::NONE as EGLint).then_some(ty)
+    }
+
+    /// The number of multisample buffers.
+    ///
+    /// Zero would mean that the config has no multisample buffer, see
+    /// [`GlConfig::num_samples`] for the number of samples in it.
+    pub fn num_sample_buffers(&self) -> u8 {
+        unsafe { self.raw_attribute(egl::SAMPLE_BUFFERS as EGLint) as u8 }
+    }
+
+    /// The unique identifier of the config from `EGL_CONFIG_ID`.
+    ///
+    /// The config could be found again with [`Display::config_by_id`].
+    pub fn config_id(&self) -> u32 {
+        self.inner.config_id() as u32
+    }
+
+    /// The compact one line summary of the config for the logs and bug
+    /// reports, like `cfg#42 RGBA8 D24S8 MSAAx4 sRGB`.
+    pub fn describe(&self) -> String {
+        let alpha = self.alpha_size();
+        let mut description = format!("cfg#{} ", self.config_id());
+        match self.color_buffer_type() {
+            Some(ColorBufferType::Rgb { r_size, g_size, b_size })
+                if r_size == g_size && g_size == b_size && (alpha == 0 || alpha == r_size) =>
+            {
+                let channels = if alpha == 0 { "RGB" } else { "RGBA" };
+                description += &format!("{channels}{r_size}");
+            },
+            Some(ColorBufferType::Rgb { r_size, g_size, b_size }) => {
+                description += &format!("R{r_size}G{g_size}B{b_size}");
+                if alpha != 0 {
+                    description += &format!("A{alpha}");
+                }
+            },
+            Some(ColorBufferType::Luminance(luminance)) => {
+                description += &format!("L{luminance}");
+                if alpha != 0 {
+                    description += &format!("A{alpha}");
+                }
+            },
+            None => description += "unknown",
+        }
+
+        if self.float_pixels() {
+            description += "F";
+        }
+
+        match (self.depth_size(), self.stencil_size()) {
+            (0, 0) => (),
+            (depth, 0) => description += &format!(" D{depth}"),
+            (0, stencil) => description += &format!(" S{stencil}"),
+            (depth, stencil) => description += &format!(" D{depth}S{stencil}"),
+        }
+
+        if self.num_samples() > 0 {
+            description += &format!(" MSAAx{}", self.num_samples());
+        }
+
+        if self.srgb_capable() {
+            description += " sRGB";
+        }
+
+        description
+    }
+
+    /// The range of the swap intervals supported by the config from
+    /// `EGL_MIN_SWAP_INTERVAL` and `EGL_MAX_SWAP_INTERVAL`.
+    ///
+    /// Some drivers only support the interval of `1`, meaning that vsync
+    /// can't be disabled.
+    pub fn swap_interval_range(&self) -> RangeInclusive<i32> {
+        unsafe {
+            let min_interval = self.raw_attribute(egl::MIN_SWAP_INTERVAL as EGLint);
+            let max_interval = self.raw_attribute(egl::MAX_SWAP_INTERVAL as EGLint);
+            min_interval..=max_interval
+        }
+    }
+
+    /// The caveat of the config from `EGL_CONFIG_CAVEAT`.
+    pub fn caveat(&self) -> ConfigCaveat {
+        match unsafe { self.raw_attribute(egl::CONFIG_CAVEAT as EGLint) } as EGLenum {
+            egl::SLOW_CONFIG => ConfigCaveat::Slow,
+            egl::NON_CONFORMANT_CONFIG => ConfigCaveat::NonConformant,
+            _ => ConfigCaveat::None,
+        }
+    }
+
+    /// The Apis the contexts created with this config are conformant to from
+    /// `EGL_CONFORMANT`.
+    pub fn conformant(&self) -> Api {
+        api_from_bits(unsafe { self.raw_attribute(egl::CONFORMANT as EGLint) as u32 })
+    }
+
+    /// Whether the native rendering Apis, like X11, could render into the
+    /// surfaces created with the config from `EGL_NATIVE_RENDERABLE`.
+    pub fn native_renderable(&self) -> bool {
+        unsafe { self.raw_attribute(egl::NATIVE_RENDERABLE as EGLint) == egl::TRUE as EGLint }
+    }
+
+    /// The transparent color of the config from `EGL_TRANSPARENT_TYPE`.
+    ///
+    /// `None` is returned when the config has no transparent color.
+    pub fn transparency(&self) -> Option<TransparentColor> {
+        unsafe {
+            if self.raw_attribute(egl::TRANSPARENT_TYPE as EGLint) != egl::TRANSPARENT_RGB as EGLint
+            {
+                return None;
+            }
+
+            Some(TransparentColor {
+                red: self.raw_attribute(egl::TRANSPARENT_RED_VALUE as EGLint) as u32,
+                green: self.raw_attribute(egl::TRANSPARENT_GREEN_VALUE as EGLint) as u32,
+                blue: self.raw_attribute(egl::TRANSPARENT_BLUE_VALUE as EGLint) as u32,
+            })
+        }
+    }
+
+    /// The pixel format of the config aggregating the attributes exposed by
+    /// [`GlConfig`], which is handy to log and compare configs.
+    pub fn pixel_format(&self) -> PixelFormat {
+        PixelFormat {
+            color_buffer_type: self.color_buffer_type(),
+            alpha_size: self.alpha_size(),
+            depth_size: self.depth_size(),
+            stencil_size: self.stencil_size(),
+            num_samples: self.num_samples(),
+            srgb_capable: self.srgb_capable(),
+            float_pixels: self.float_pixels(),
+        }
+    }
+
+    /// The type of the color buffer components.
+    ///
+    /// [`ColorComponentType::Fixed`] is returned when
+    /// `EGL_EXT_pixel_format_float` is not supported.
+    pub fn color_component_type(&self) -> ColorComponentType {
+        if self.float_pixels() {
+            ColorComponentType::Float
+        } else {
+            ColorComponentType::Fixed
+        }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
-    unsafe fn raw_attribute(&self, attr: EGLint) -> EGLint {
+    pub(crate) unsafe fn raw_attribute(&self, attr: EGLint) -> EGLint {
         unsafe {
             let mut val = 0;
             self.inner.display.inner.egl.GetConfigAttrib(
@@ -254,6 +471,70 @@ impl Config {
     }
 }
 
+/// The transparent color of the [`Config`].
+///
+/// The pixels of that color are transparent when the surface is presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransparentColor {
+    /// The red component of the color.
+    pub red: u32,
+
+    /// The green component of the color.
+    pub green: u32,
+
+    /// The blue component of the color.
+    pub blue: u32,
+}
+
+/// The caveat of the [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigCaveat {
+    /// The config has no caveats.
+    None,
+
+    /// Rendering with the config may run at reduced performance, for example
+    /// when it's not hardware accelerated.
+    Slow,
+
+    /// The config doesn't pass the conformance tests.
+    NonConformant,
+}
+
+/// The pixel format of the [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelFormat {
+    /// The type of the underlying color buffer.
+    pub color_buffer_type: Option<ColorBufferType>,
+
+    /// The size of the alpha.
+    pub alpha_size: u8,
+
+    /// The size of the depth buffer.
+    pub depth_size: u8,
+
+    /// The size of the stencil buffer.
+    pub stencil_size: u8,
+
+    /// The number of samples in multisample buffer.
+    pub num_samples: u8,
+
+    /// Whether the config supports creating srgb capable surfaces.
+    pub srgb_capable: bool,
+
+    /// Whether the config uses floating pixels.
+    pub float_pixels: bool,
+}
+
+/// The type of the color buffer components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorComponentType {
+    /// The components are fixed point values.
+    Fixed,
+
+    /// The components are floating point values.
+    Float,
+}
+
 impl GlConfig for Config {
     fn color_buffer_type(&self) -> Option<ColorBufferType> {
         unsafe {
@@ -347,22 +628,7 @@ impl GlConfig for Config {
     }
 
     fn api(&self) -> Api {
-        let mut api = Api::empty();
-        let raw_api = unsafe { self.raw_attribute(egl::RENDERABLE_TYPE as EGLint) as u32 };
-        if raw_api & egl::OPENGL_BIT as u32 != 0 {
-            api.insert(Api::OPENGL);
-        }
-        if raw_api & egl::OPENGL_ES_BIT as u32 != 0 {
-            api.insert(Api::GLES1);
-        }
-        if raw_api & egl::OPENGL_ES2_BIT as u32 != 0 {
-            api.insert(Api::GLES2);
-        }
-        if raw_api & egl::OPENGL_ES3_BIT as u32 != 0 {
-            api.insert(Api::GLES3);
-        }
-
-        api
+        api_from_bits(unsafe { self.raw_attribute(egl::RENDERABLE_TYPE as EGLint) as u32 })
     }
 }
 
@@ -395,19 +661,83 @@ impl X11GlConfigExt for Config {
 
 impl Sealed for Config {}
 
+/// Convert the `EGL_RENDERABLE_TYPE` like bits to the [`Api`].
+fn api_from_bits(raw_api: u32) -> Api {
+    let mut api = Api::empty();
+    if raw_api & egl::OPENGL_BIT as u32 != 0 {
+        api.insert(Api::OPENGL);
+    }
+    if raw_api & egl::OPENGL_ES_BIT as u32 != 0 {
+        api.insert(Api::GLES1);
+    }
+    if raw_api & egl::OPENGL_ES2_BIT as u32 != 0 {
+        api.insert(Api::GLES2);
+    }
+    if raw_api & egl::OPENGL_ES3_BIT as u32 != 0 {
+        api.insert(Api::GLES3);
+    }
+
+    api
+}
+
 pub(crate) struct ConfigInner {
     display: Display,
     pub(crate) raw: EglConfig,
 }
 
+impl ConfigInner {
+    fn config_id(&self) -> EGLint {
+        unsafe {
+            let mut config_id = 0;
+            self.display.inner.egl.GetConfigAttrib(
+                *self.display.inner.raw,
+                *self.raw,
+                egl::CONFIG_ID as EGLint,
+                &mut config_id,
+            );
+            config_id
+        }
+    }
+}
+
 impl PartialEq for ConfigInner {
     fn eq(&self, other: &Self) -> bool {
-        self.raw == other.raw
+        same_config(
+            (*self.display.inner.raw, *self.raw),
+            (*other.display.inner.raw, *other.raw),
+            || (self.config_id(), other.config_id()),
+        )
     }
 }
 
+/// Whether the `(display, config)` handles refer to the same config, querying
+/// their `EGL_CONFIG_ID` with `config_ids` only when the config handles differ.
+fn same_config(
+    lhs: (EGLDisplay, EGLConfig),
+    rhs: (EGLDisplay, EGLConfig),
+    config_ids: impl FnOnce() -> (EGLint, EGLint),
+) -> bool {
+    if lhs.0 != rhs.0 {
+        return false;
+    }
+
+    if lhs.1 == rhs.1 {
+        return true;
+    }
+
+    let (lhs_id, rhs_id) = config_ids();
+    lhs_id == rhs_id
+}
+
 impl Eq for ConfigInner {}
 
+impl Hash for ConfigInner {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.display.inner.raw).hash(state);
+        self.config_id().hash(state);
+    }
+}
+
 impl fmt::Debug for ConfigInner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Config")
@@ -430,3 +760,49 @@ impl Deref for EglConfig {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+    use crate::config::ConfigTemplateBuilder;
+
+    fn hash(config: &Config) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        config.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn same_config_compares_ids_of_distinct_handles() {
+        let display = 1usize as EGLDisplay;
+        let other_display = 2usize as EGLDisplay;
+        let config = 1usize as EGLConfig;
+        let other_config = 2usize as EGLConfig;
+
+        // The same handles are equal without querying the ids.
+        assert!(same_config((display, config), (display, config), || unreachable!()));
+
+        // The distinct handles are compared by their ids.
+        assert!(same_config((display, config), (display, other_config), || (7, 7)));
+        assert!(!same_config((display, config), (display, other_config), || (7, 8)));
+
+        // The configs of different displays are never equal.
+        assert!(!same_config((display, config), (other_display, config), || (7, 7)));
+    }
+
+    #[test]
+    #[ignore = "requires an EGL device"]
+    fn hash_agrees_with_eq() {
+        let display = super::super::test_display().expect("no EGL device");
+
+        let template = ConfigTemplateBuilder::new().build();
+        let config = unsafe { display.find_configs(template) }.unwrap().next().expect("no config");
+
+        // Obtain the same config again by its id.
+        let same_config = display.config_by_id(config.config_id()).unwrap();
+        assert_eq!(config, same_config);
+        assert_eq!(hash(&config), hash(&same_config));
+    }
+}
@@ -1,25 +1,49 @@
 //! Everything related to `EGLContext` management.
 
-use std::fmt;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::{self, CStr, CString};
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::{fmt, mem, ptr};
 
-use glutin_egl_sys::egl::types::{EGLenum, EGLint};
-use glutin_egl_sys::{egl, EGLContext};
+use glutin_egl_sys::egl::types::{EGLSurface, EGLenum, EGLint};
+use glutin_egl_sys::{egl, EGLContext, EGLDisplay};
 
 use crate::config::{Api, GetGlConfig};
 use crate::context::{
-    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, RawContext, Robustness, Version,
+    self, AsRawContext, ContextApi, ContextAttributes, ContextPriority, GlProfile, RawContext,
+    ReleaseBehavior, ResetStatus, Robustness, Version,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
-use crate::error::{ErrorKind, Result};
+use crate::error::{Error, ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::SurfaceTypeTrait;
 
 use super::config::Config;
 use super::display::Display;
-use super::surface::Surface;
+use super::surface::{RenderBuffer, Surface};
+
+type GlGetGraphicsResetStatus = unsafe extern "system" fn() -> u32;
+type GlGetString = unsafe extern "system" fn(u32) -> *const u8;
+type GlGetIntegerv = unsafe extern "system" fn(u32, *mut i32);
+type GlVoidFn = unsafe extern "system" fn();
+
+const GL_RENDERER: u32 = 0x1F01;
+const GL_VERSION: u32 = 0x1F02;
+const GL_EXTENSIONS: u32 = 0x1F03;
+
+const GL_CONTEXT_FLAGS: u32 = 0x821E;
+const GL_CONTEXT_FLAG_ROBUST_ACCESS_BIT: i32 = 0x4;
+const GL_CONTEXT_FLAG_NO_ERROR_BIT: i32 = 0x8;
+const GL_CONTEXT_ROBUST_ACCESS: u32 = 0x90F3;
+const GL_RESET_NOTIFICATION_STRATEGY: u32 = 0x8256;
+const GL_LOSE_CONTEXT_ON_RESET: i32 = 0x8252;
+
+const GL_GUILTY_CONTEXT_RESET: u32 = 0x8253;
+const GL_INNOCENT_CONTEXT_RESET: u32 = 0x8254;
+const GL_UNKNOWN_CONTEXT_RESET: u32 = 0x8255;
 
 impl Display {
     pub(crate) unsafe fn create_context(
@@ -27,10 +51,52 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        unsafe { self.create_context_with_config(Some(config), context_attributes) }
+    }
+
+    /// Create the context not tied to any config with
+    /// `EGL_KHR_no_config_context`, so it could be made current with
+    /// surfaces of different configs.
+    ///
+    /// The context could be created for any Api supported by the display,
+    /// when the Api is not requested the one bound on the calling thread is
+    /// used. Such context doesn't have a config, see [`NoConfig`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_context_without_config(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext<NoConfig>> {
+        if !self.inner.display_extensions.contains("EGL_KHR_no_config_context") {
+            return Err(
+                ErrorKind::NotSupported("EGL_KHR_no_config_context is not supported").into()
+            );
+        }
+
+        unsafe { self.create_context_with_config(None, context_attributes) }
+    }
+
+    unsafe fn create_context_with_config<C>(
+        &self,
+        config: Option<&Config>,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext<C>> {
         let mut attrs = Vec::<EGLint>::new();
 
         let supports_opengl = self.inner.version > Version::new(1, 3);
-        let config_api = config.api();
+        let config_api = match config {
+            Some(config) => config.api(),
+            // Without the config any Api could be requested explicitly.
+            None if context_attributes.api.is_some() => Api::all(),
+            // Otherwise use the Api bound on the calling thread, which is
+            // OpenGL ES by default.
+            None => match self.bound_api() {
+                Some(ContextApi::OpenGl(_)) => Api::OPENGL,
+                _ => Api::GLES2,
+            },
+        };
 
         let (api, mut version) = match context_attributes.api {
             api @ Some(ContextApi::OpenGl(_)) | api @ None
@@ -54,7 +120,9 @@ impl Display {
             },
         };
 
+        let mut robustness = Robustness::NotRobust;
         let is_one_five = self.inner.version >= Version::new(1, 5);
+        let has_robustsess = self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS);
         if is_one_five || self.inner.display_extensions.contains("EGL_KHR_create_context") {
             let mut flags = 0;
 
@@ -70,6 +138,10 @@ impl Display {
 
                 attrs.push(egl::CONTEXT_OPENGL_PROFILE_MASK as EGLint);
                 attrs.push(profile as EGLint);
+
+                if context_attributes.forward_compatible {
+                    flags |= egl::CONTEXT_OPENGL_FORWARD_COMPATIBLE_BIT_KHR as EGLint;
+                }
             }
 
             if let Some(version) = version {
@@ -79,27 +151,35 @@ impl Display {
                 attrs.push(version.minor as EGLint);
             }
 
-            let has_robustsess = self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS);
+            robustness = push_khr_context_attributes(
+                &mut attrs,
+                self.inner.version,
+                self.inner.features,
+                context_attributes,
+                flags,
+            )?;
+        } else if self.inner.version >= Version::new(1, 3) {
+            // EGL 1.3 uses that to indicate client version instead of major/minor. The
+            // constant is the same as `CONTEXT_MAJOR_VERSION`.
+            if let Some(version) = version {
+                attrs.push(egl::CONTEXT_CLIENT_VERSION as EGLint);
+                attrs.push(version.major as EGLint);
+            }
 
-            let mut requested_no_error = false;
             match context_attributes.robustness {
                 Robustness::NotRobust => (),
-                Robustness::NoError
-                    if self.inner.features.contains(DisplayFeatures::CONTEXT_NO_ERROR) =>
+                robust @ (Robustness::RobustLoseContextOnReset
+                | Robustness::RobustNoResetNotification)
+                    if has_robustsess =>
                 {
-                    attrs.push(egl::CONTEXT_OPENGL_NO_ERROR_KHR as EGLint);
-                    attrs.push(egl::TRUE as EGLint);
-                    requested_no_error = true;
+                    push_ext_robustness(&mut attrs, robust);
+                    robustness = robust;
                 },
-                Robustness::RobustLoseContextOnReset if has_robustsess => {
-                    attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
-                    attrs.push(egl::LOSE_CONTEXT_ON_RESET as EGLint);
-                    flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
-                },
-                Robustness::RobustNoResetNotification if has_robustsess => {
-                    attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
-                    attrs.push(egl::NO_RESET_NOTIFICATION as EGLint);
-                    flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
+                Robustness::NoError => {
+                    return Err(ErrorKind::NotSupported(
+                        "EGL_KHR_create_context_no_error is not supported",
+                    )
+                    .into())
                 },
                 _ => {
                     return Err(
@@ -107,23 +187,66 @@ impl Display {
                     )
                 },
             }
+        } else if context_attributes.robustness != Robustness::NotRobust {
+            return Err(ErrorKind::NotSupported("context robustness is not supported").into());
+        }
 
-            if context_attributes.debug && is_one_five && !requested_no_error {
-                attrs.push(egl::CONTEXT_OPENGL_DEBUG as EGLint);
-                attrs.push(egl::TRUE as EGLint);
+        // Flush control.
+        if self.inner.features.contains(DisplayFeatures::CONTEXT_RELEASE_BEHAVIOR) {
+            match context_attributes.release_behavior {
+                // This is the default behavior in specification.
+                ReleaseBehavior::Flush => (),
+                ReleaseBehavior::None => {
+                    attrs.push(egl::CONTEXT_RELEASE_BEHAVIOR_KHR as EGLint);
+                    attrs.push(egl::CONTEXT_RELEASE_BEHAVIOR_NONE_KHR as EGLint);
+                },
             }
+        } else if context_attributes.release_behavior != ReleaseBehavior::Flush {
+            return Err(ErrorKind::NotSupported(
+                "flush control behavior EGL_KHR_context_flush_control",
+            )
+            .into());
+        }
+
+        if let Some(priority) = context_attributes.priority {
+            if self.inner.display_extensions.contains("EGL_IMG_context_priority") {
+                let priority = match priority {
+                    ContextPriority::Low => egl::CONTEXT_PRIORITY_LOW_IMG,
+                    ContextPriority::Medium => egl::CONTEXT_PRIORITY_MEDIUM_IMG,
+                    ContextPriority::High => egl::CONTEXT_PRIORITY_HIGH_IMG,
+                };
 
-            if flags != 0 {
-                attrs.push(egl::CONTEXT_FLAGS_KHR as EGLint);
-                attrs.push(flags as EGLint);
+                attrs.push(egl::CONTEXT_PRIORITY_LEVEL_IMG as EGLint);
+                attrs.push(priority as EGLint);
+            } else if priority != ContextPriority::Medium {
+                return Err(
+                    ErrorKind::NotSupported("EGL_IMG_context_priority is not supported").into()
+                );
             }
-        } else if self.inner.version >= Version::new(1, 3) {
-            // EGL 1.3 uses that to indicate client version instead of major/minor. The
-            // constant is the same as `CONTEXT_MAJOR_VERSION`.
-            if let Some(version) = version {
-                attrs.push(egl::CONTEXT_CLIENT_VERSION as EGLint);
-                attrs.push(version.major as EGLint);
+        }
+
+        if context_attributes.protected_content {
+            if !self.inner.display_extensions.contains("EGL_EXT_protected_content") {
+                return Err(
+                    ErrorKind::NotSupported("EGL_EXT_protected_content is not supported").into()
+                );
             }
+
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
+        for &(attribute, value) in &context_attributes.raw_attributes {
+            if attrs.chunks_exact(2).any(|pair| pair[0] == attribute) {
+                return Err(Error::new(
+                    None,
+                    Some(format!("the raw context attribute {attribute:#x} is already set")),
+                    ErrorKind::BadAttribute,
+                ));
+            }
+
+            attrs.push(attribute);
+            attrs.push(value);
         }
 
         attrs.push(egl::NONE as EGLint);
@@ -132,7 +255,29 @@ impl Display {
             context_attributes.shared_context.as_ref()
         {
             match shared_context {
-                RawContext::Egl(shared_context) => *shared_context,
+                RawContext::Egl(shared_context) => {
+                    // Querying a context not created on this display fails with
+                    // `EGL_BAD_CONTEXT`, so use it to catch sharing across displays
+                    // before the driver reports a vague `EGL_BAD_MATCH`.
+                    let mut config_id = 0;
+                    let is_same_display = unsafe {
+                        self.inner.egl.QueryContext(
+                            *self.inner.raw,
+                            *shared_context,
+                            egl::CONFIG_ID as EGLint,
+                            &mut config_id,
+                        ) != egl::FALSE
+                    };
+
+                    if !is_same_display {
+                        return Err(ErrorKind::NotSupported(
+                            "shared context from a different display",
+                        )
+                        .into());
+                    }
+
+                    *shared_context
+                },
                 #[allow(unreachable_patterns)]
                 _ => return Err(ErrorKind::NotSupported("passed incompatible raw context").into()),
             }
@@ -141,76 +286,200 @@ impl Display {
         };
 
         // Bind the api.
+        let _guard = self.inner.lock();
         unsafe {
+            let previous_api = self.inner.egl.QueryAPI();
             if self.inner.egl.BindAPI(api) == egl::FALSE {
                 return Err(super::check_error().err().unwrap());
             }
 
-            let config = config.clone();
+            let config = config.cloned();
+            // `EGL_NO_CONFIG_KHR` is a null config.
+            let raw_config = config.as_ref().map_or(ptr::null(), |config| *config.inner.raw);
             let context = self.inner.egl.CreateContext(
                 *self.inner.raw,
-                *config.inner.raw,
+                raw_config,
                 shared_context,
                 attrs.as_ptr(),
             );
 
             if context == egl::NO_CONTEXT {
-                return Err(super::check_error().err().unwrap());
+                // Query the error first, since binding the Api resets it. The
+                // `EGL_BAD_ALLOC` is reported as `ErrorKind::OutOfMemory`, so
+                // the creation could be retried once the memory is freed.
+                let err = super::check_error().err().unwrap();
+
+                // Don't leave the thread bound to the unexpected Api.
+                self.inner.egl.BindAPI(previous_api);
+
+                return Err(err);
             }
 
-            let inner =
-                ContextInner { display: self.clone(), config, raw: EglContext(context), api };
+            // Restore the Api used by the caller, so interleaving GL and GLES
+            // contexts on the same thread doesn't change the Api behind its
+            // back. The context binds its own Api when it's used.
+            self.inner.egl.BindAPI(previous_api);
+
+            let inner = ContextInner {
+                display: self.clone(),
+                config,
+                raw: EglContext(context),
+                shared_context: (shared_context != egl::NO_CONTEXT)
+                    .then_some(EglContext(shared_context)),
+                api,
+                robustness,
+                label: Cell::new(None),
+                owned: true,
+            };
             Ok(NotCurrentContext::new(inner))
         }
     }
 }
 
 /// A wrapper around `EGLContext` that is known to be not current.
+///
+/// The context could be sent to the different thread, for example to make it
+/// current on the render thread after
+/// [`PossiblyCurrentGlContext::make_not_current`].
+///
+/// ```no_run
+/// fn test_send<T: Send>() {}
+/// test_send::<glutin::api::egl::context::NotCurrentContext>();
+/// ```
+///
+/// The contexts created with [`Display::create_context_without_config`] are
+/// `NotCurrentContext<NoConfig>`, which doesn't implement [`GetGlConfig`].
 #[derive(Debug)]
-pub struct NotCurrentContext {
+pub struct NotCurrentContext<C = Config> {
     inner: ContextInner,
+    _config: PhantomData<fn() -> C>,
 }
 
-impl NotCurrentContext {
+/// The marker for the contexts created with
+/// [`Display::create_context_without_config`], which could be made current
+/// with surfaces of any config of the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoConfig;
+
+impl<C> NotCurrentContext<C> {
     /// Make a [`Self::PossiblyCurrentContext`] indicating that the context
     /// could be current on the thread.
-    pub fn make_current_surfaceless(self) -> Result<PossiblyCurrentContext> {
+    ///
+    /// The context is made current without any draw and read surfaces, which
+    /// requires `EGL_KHR_surfaceless_context`.
+    pub fn make_current_surfaceless(self) -> Result<PossiblyCurrentContext<C>> {
         self.inner.make_current_surfaceless()?;
-        Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
+        Ok(PossiblyCurrentContext::new(self.inner))
+    }
+
+    /// The priority granted to the context by the driver.
+    ///
+    /// The driver may silently grant lower priority than was requested with
+    /// [`ContextAttributesBuilder::with_priority`]. `None` is returned when
+    /// `EGL_IMG_context_priority` is not supported.
+    ///
+    /// [`ContextAttributesBuilder::with_priority`]: crate::context::ContextAttributesBuilder::with_priority
+    pub fn priority(&self) -> Option<ContextPriority> {
+        self.inner.priority()
+    }
+
+    /// The context this context was created to share the objects with.
+    ///
+    /// `None` is returned when the context doesn't share the objects or when
+    /// it was created with [`Self::from_raw`], since EGL can't report that.
+    pub fn shared_context(&self) -> Option<RawContext> {
+        self.inner.shared_context()
+    }
+
+    /// Create the new context sharing the objects with this context, using
+    /// the same display and config.
+    ///
+    /// The `shared_context` from the `context_attributes` is replaced with
+    /// this context. This is the recommended way to create the contexts for
+    /// the resource loading threads.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_shared(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext<C>> {
+        unsafe { self.inner.create_shared(context_attributes) }
     }
 
     fn new(inner: ContextInner) -> Self {
-        Self { inner }
+        Self { inner, _config: PhantomData }
     }
 }
 
-impl NotCurrentGlContext for NotCurrentContext {
-    type PossiblyCurrentContext = PossiblyCurrentContext;
+impl NotCurrentContext {
+    /// Wrap the externally created `EGLContext`.
+    ///
+    /// The context is not destroyed when the wrapper is dropped, its owner
+    /// remains responsible for that. The robustness of the context is
+    /// assumed to be [`Robustness::NotRobust`].
+    ///
+    /// # Safety
+    ///
+    /// The `raw` context must be created on the `display` with the `config`
+    /// and outlive the returned context.
+    pub unsafe fn from_raw(display: &Display, config: &Config, raw: EGLContext) -> Result<Self> {
+        let mut client_type = 0;
+        if unsafe {
+            display.inner.egl.QueryContext(
+                *display.inner.raw,
+                raw,
+                egl::CONTEXT_CLIENT_TYPE as EGLint,
+                &mut client_type,
+            )
+        } == egl::FALSE
+        {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        let inner = ContextInner {
+            display: display.clone(),
+            config: Some(config.clone()),
+            raw: EglContext(raw),
+            shared_context: None,
+            api: client_type as EGLenum,
+            robustness: Robustness::NotRobust,
+            label: Cell::new(None),
+            owned: false,
+        };
+
+        Ok(Self::new(inner))
+    }
+}
+
+impl<C> NotCurrentGlContext for NotCurrentContext<C> {
+    type PossiblyCurrentContext = PossiblyCurrentContext<C>;
     type Surface<T: SurfaceTypeTrait> = Surface<T>;
 
     fn treat_as_possibly_current(self) -> Self::PossiblyCurrentContext {
-        PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData }
+        PossiblyCurrentContext::new(self.inner)
     }
 
     fn make_current<T: SurfaceTypeTrait>(
         self,
         surface: &Surface<T>,
-    ) -> Result<PossiblyCurrentContext> {
+    ) -> Result<PossiblyCurrentContext<C>> {
         self.inner.make_current_draw_read(surface, surface)?;
-        Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
+        Ok(PossiblyCurrentContext::new(self.inner))
     }
 
     fn make_current_draw_read<T: SurfaceTypeTrait>(
         self,
         surface_draw: &Surface<T>,
         surface_read: &Surface<T>,
-    ) -> Result<PossiblyCurrentContext> {
+    ) -> Result<PossiblyCurrentContext<C>> {
         self.inner.make_current_draw_read(surface_draw, surface_read)?;
-        Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
+        Ok(PossiblyCurrentContext::new(self.inner))
     }
 }
 
-impl GlContext for NotCurrentContext {
+impl<C> GlContext for NotCurrentContext<C> {
     fn context_api(&self) -> ContextApi {
         self.inner.context_api()
     }
@@ -220,11 +489,11 @@ impl GetGlConfig for NotCurrentContext {
     type Target = Config;
 
     fn config(&self) -> Self::Target {
-        self.inner.config.clone()
+        self.inner.config()
     }
 }
 
-impl GetGlDisplay for NotCurrentContext {
+impl<C> GetGlDisplay for NotCurrentContext<C> {
     type Target = Display;
 
     fn display(&self) -> Self::Target {
@@ -232,30 +501,291 @@ impl GetGlDisplay for NotCurrentContext {
     }
 }
 
-impl AsRawContext for NotCurrentContext {
+impl<C> AsRawContext for NotCurrentContext<C> {
     fn raw_context(&self) -> RawContext {
         RawContext::Egl(*self.inner.raw)
     }
 }
 
-impl Sealed for NotCurrentContext {}
+impl<C> Sealed for NotCurrentContext<C> {}
 
 /// A wrapper around `EGLContext` that could be current for the current thread.
+///
+/// The context is not `Send`, make it not current first to move it to the
+/// different thread.
+///
+/// ```compile_fail
+/// fn test_send<T: Send>() {}
+/// test_send::<glutin::api::egl::context::PossiblyCurrentContext>();
+/// ```
+///
+/// See [`NotCurrentContext`] for the contexts without config.
 #[derive(Debug)]
-pub struct PossiblyCurrentContext {
+pub struct PossiblyCurrentContext<C = Config> {
     pub(crate) inner: ContextInner,
     _nosendsync: PhantomData<EGLContext>,
+    _config: PhantomData<fn() -> C>,
 }
 
-impl PossiblyCurrentContext {
+impl<C> PossiblyCurrentContext<C> {
     /// Make this context current on the calling thread.
+    ///
+    /// See [`NotCurrentContext::make_current_surfaceless`] for details.
     pub fn make_current_surfaceless(&self) -> Result<()> {
         self.inner.make_current_surfaceless()
     }
+
+    /// Make the context not current, tolerating the current surfaces which
+    /// were already destroyed.
+    ///
+    /// Unlike [`PossiblyCurrentGlContext::make_not_current`], when the driver
+    /// fails with `EGL_BAD_SURFACE` or `EGL_BAD_CURRENT_SURFACE`, which could
+    /// happen after the output removal on Wayland, the context is made current
+    /// without any surfaces and then released. This requires
+    /// `EGL_KHR_surfaceless_context`, otherwise the original error is
+    /// returned. Other errors are returned as is.
+    pub fn make_not_current_robust(self) -> Result<NotCurrentContext<C>> {
+        self.inner.make_not_current_robust()?;
+        Ok(NotCurrentContext::new(self.inner))
+    }
+
+    /// The buffer the context renders into when it's current with the window
+    /// surface, which could differ from [`Surface::render_buffer`] when the
+    /// driver doesn't honor the single buffer request.
+    ///
+    /// `None` is returned when the context is not bound to the surface.
+    pub fn render_buffer(&self) -> Option<RenderBuffer> {
+        self.inner
+            .query_attribute(egl::RENDER_BUFFER as EGLint)
+            .filter(|render_buffer| *render_buffer != egl::NONE as EGLint)
+            .map(RenderBuffer::from_raw)
+    }
+
+    /// The priority granted to the context by the driver.
+    ///
+    /// See [`NotCurrentContext::priority`] for details.
+    pub fn priority(&self) -> Option<ContextPriority> {
+        self.inner.priority()
+    }
+
+    /// The context this context was created to share the objects with.
+    ///
+    /// See [`NotCurrentContext::shared_context`] for details.
+    pub fn shared_context(&self) -> Option<RawContext> {
+        self.inner.shared_context()
+    }
+
+    /// Create the new context sharing the objects with this context.
+    ///
+    /// See [`NotCurrentContext::create_shared`] for details.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_context`].
+    pub unsafe fn create_shared(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext<C>> {
+        unsafe { self.inner.create_shared(context_attributes) }
+    }
+
+    /// Make the context current with the `surface` until the returned guard
+    /// is dropped, which restores the context and surfaces that were current
+    /// on the calling thread before.
+    ///
+    /// This is useful for the code which must temporarily make its context
+    /// current without disturbing the binding of the caller, even when it
+    /// returns early on errors.
+    pub fn make_current_scoped<T: SurfaceTypeTrait>(
+        &self,
+        surface: &Surface<T>,
+    ) -> Result<CurrentGuard<'_>> {
+        let egl = &self.inner.display.inner.egl;
+        let previous_api = unsafe { egl.QueryAPI() };
+
+        // The current context is tracked per Api, so query it for ours.
+        self.inner.bind_api();
+        let (display, draw, read, context) = unsafe {
+            (
+                egl.GetCurrentDisplay(),
+                egl.GetCurrentSurface(egl::DRAW as EGLint),
+                egl.GetCurrentSurface(egl::READ as EGLint),
+                egl.GetCurrentContext(),
+            )
+        };
+
+        let guard =
+            CurrentGuard { inner: &self.inner, previous_api, display, draw, read, raw: context };
+        self.inner.make_current_draw_read(surface, surface)?;
+        Ok(guard)
+    }
+
+    /// Whether the context could be made current with the `surface`, without
+    /// calling `eglMakeCurrent`.
+    ///
+    /// The configs of the context and the surface must be compatible and the
+    /// surface config must be renderable with the Api of the context. See
+    /// [`Surface::is_compatible_with`].
+    pub fn is_surface_compatible<T: SurfaceTypeTrait>(&self, surface: &Surface<T>) -> bool {
+        surface.is_compatible_with(self)
+    }
+
+    /// The version of the created context.
+    ///
+    /// The driver may create a context with higher version than was requested.
+    /// The version is parsed from `GL_VERSION`, since `eglQueryContext` can't
+    /// report it, which requires the context to be current on the calling
+    /// thread.
+    pub fn context_version(&self) -> Result<Version> {
+        self.inner.context_version()
+    }
+
+    /// Query whether the graphics reset happened since the last call with
+    /// `glGetGraphicsResetStatus`.
+    ///
+    /// When the reset is reported the context must be recreated, since all
+    /// its resources are lost.
+    ///
+    /// This function returns [`Err`] when the context wasn't created with
+    /// robust access, or when it's not current on the calling thread.
+    pub fn reset_status(&self) -> Result<ResetStatus> {
+        self.inner.reset_status()
+    }
+
+    /// The robustness granted to the context by the driver.
+    ///
+    /// Some drivers grant robust access, but ignore the requested reset
+    /// notification strategy. Since EGL can't report that, the context flags
+    /// are queried from GL, which requires the context to be current on the
+    /// calling thread.
+    ///
+    /// The robustness requested at creation is returned when the context is
+    /// too old to report it, that is before OpenGL 3.0 or OpenGL ES 3.2
+    /// without `GL_EXT_robustness`.
+    pub fn robustness(&self) -> Result<Robustness> {
+        self.inner.granted_robustness()
+    }
+
+    /// The `GL_RENDERER` string of the context, like `llvmpipe (LLVM 15.0.7,
+    /// 256 bits)`.
+    ///
+    /// This function returns [`Err`] when the context is not current on the
+    /// calling thread.
+    pub fn renderer(&self) -> Result<String> {
+        self.inner.gl_string(GL_RENDERER)
+    }
+
+    /// Whether the context is rendered by the hardware and not by the
+    /// software rasterizer, like `llvmpipe`, `softpipe` or `swrast`.
+    ///
+    /// The check inspects the [`Self::renderer`], thus the context must be
+    /// current on the calling thread. Use [`Display::is_hardware_accelerated`]
+    /// when there's no current context.
+    pub fn is_hardware_accelerated(&self) -> Result<bool> {
+        self.renderer().map(|renderer| !super::display::is_software_renderer(&renderer))
+    }
+
+    /// Flush the GL commands issued on the context with `glFlush`, so they
+    /// are executed in finite time.
+    ///
+    /// This function returns [`Err`] when the context is not current on the
+    /// calling thread.
+    pub fn flush(&self) -> Result<()> {
+        self.inner.call_gl_fn(b"glFlush\0")
+    }
+
+    /// Block until all the GL commands issued on the context are complete
+    /// with `glFinish`.
+    ///
+    /// Unlike `eglWaitClient`, this doesn't order the GL rendering against the
+    /// native rendering into the surface, like X11 drawing into the pixmap,
+    /// it only waits for the GL. This function returns [`Err`] when the
+    /// context is not current on the calling thread.
+    pub fn finish(&self) -> Result<()> {
+        self.inner.call_gl_fn(b"glFinish\0")
+    }
+
+    /// Attach the `label` to the context, which is reported by the debug tools
+    /// and passed to the callback registered with
+    /// [`Display::enable_debug_callback`].
+    ///
+    /// This requires `EGL_KHR_debug`.
+    pub fn set_label(&self, label: &str) -> Result<()> {
+        super::debug::label_object(
+            &self.inner.display,
+            egl::OBJECT_CONTEXT_KHR,
+            *self.inner.raw,
+            &self.inner.label,
+            label,
+        )
+    }
+
+    /// The raw `EGLSurface` bound for drawing along with this context on the
+    /// calling thread.
+    ///
+    /// Returns `None` when no surface is bound or when the context is not
+    /// current on the calling thread.
+    pub fn current_draw_surface(&self) -> Option<*const ffi::c_void> {
+        self.inner.current_surface(egl::DRAW)
+    }
+
+    /// The raw `EGLSurface` bound for reading along with this context on the
+    /// calling thread.
+    ///
+    /// See [`Self::current_draw_surface`] for details.
+    pub fn current_read_surface(&self) -> Option<*const ffi::c_void> {
+        self.inner.current_surface(egl::READ)
+    }
+
+    /// Resolve the addresses of all the `names` at once, in the same order.
+    ///
+    /// This is the same as calling [`GlDisplay::get_proc_address`] on the
+    /// display of the context for each name, but the lookup state is shared
+    /// between the calls, which is useful for the loaders resolving hundreds
+    /// of functions. The unresolved functions are returned as null pointers.
+    ///
+    /// [`GlDisplay::get_proc_address`]: crate::display::GlDisplay::get_proc_address
+    pub fn get_proc_addresses(&self, names: &[&CStr]) -> Vec<*const ffi::c_void> {
+        self.inner.display.get_proc_addresses(names)
+    }
+
+    /// Resolve the addresses of all the `names`, failing when any of them is
+    /// missing.
+    ///
+    /// When the `version` is provided, the [`Self::context_version`] is checked
+    /// to be at least that version first and [`ErrorKind::NotSupported`] is
+    /// returned otherwise. The first unresolved function is reported with
+    /// [`ErrorKind::NotFound`].
+    pub fn load_fn_table<'a>(
+        &self,
+        version: Option<Version>,
+        names: &[&'a CStr],
+    ) -> Result<HashMap<&'a CStr, *const ffi::c_void>> {
+        if let Some(version) = version {
+            if self.context_version()? < version {
+                return Err(ErrorKind::NotSupported(
+                    "the context version is lower than the requested one",
+                )
+                .into());
+            }
+        }
+
+        let addresses = self.get_proc_addresses(names);
+        if let Some(position) = addresses.iter().position(|address| address.is_null()) {
+            let message = format!("missing entry point {:?}", names[position]);
+            return Err(Error::new(None, Some(message), ErrorKind::NotFound));
+        }
+
+        Ok(names.iter().copied().zip(addresses).collect())
+    }
+
+    fn new(inner: ContextInner) -> Self {
+        Self { inner, _nosendsync: PhantomData, _config: PhantomData }
+    }
 }
 
-impl PossiblyCurrentGlContext for PossiblyCurrentContext {
-    type NotCurrentContext = NotCurrentContext;
+impl<C> PossiblyCurrentGlContext for PossiblyCurrentContext<C> {
+    type NotCurrentContext = NotCurrentContext<C>;
     type Surface<T: SurfaceTypeTrait> = Surface<T>;
 
     fn make_not_current(self) -> Result<Self::NotCurrentContext> {
@@ -283,7 +813,7 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
     }
 }
 
-impl GlContext for PossiblyCurrentContext {
+impl<C> GlContext for PossiblyCurrentContext<C> {
     fn context_api(&self) -> ContextApi {
         self.inner.context_api()
     }
@@ -293,11 +823,11 @@ impl GetGlConfig for PossiblyCurrentContext {
     type Target = Config;
 
     fn config(&self) -> Self::Target {
-        self.inner.config.clone()
+        self.inner.config()
     }
 }
 
-impl GetGlDisplay for PossiblyCurrentContext {
+impl<C> GetGlDisplay for PossiblyCurrentContext<C> {
     type Target = Display;
 
     fn display(&self) -> Self::Target {
@@ -305,23 +835,99 @@ impl GetGlDisplay for PossiblyCurrentContext {
     }
 }
 
-impl AsRawContext for PossiblyCurrentContext {
+impl<C> AsRawContext for PossiblyCurrentContext<C> {
     fn raw_context(&self) -> RawContext {
         RawContext::Egl(*self.inner.raw)
     }
 }
 
-impl Sealed for PossiblyCurrentContext {}
+impl<C> Sealed for PossiblyCurrentContext<C> {}
+
+/// The guard restoring the previous current context on drop, see
+/// [`PossiblyCurrentContext::make_current_scoped`].
+#[derive(Debug)]
+pub struct CurrentGuard<'a> {
+    inner: &'a ContextInner,
+    previous_api: EGLenum,
+    display: EGLDisplay,
+    draw: EGLSurface,
+    read: EGLSurface,
+    raw: EGLContext,
+}
+
+impl Drop for CurrentGuard<'_> {
+    fn drop(&mut self) {
+        let inner = self.inner;
+        let egl = &inner.display.inner.egl;
+        unsafe {
+            inner.bind_api();
+
+            // Release our context when nothing was current before.
+            let display = if self.display == egl::NO_DISPLAY {
+                *inner.display.inner.raw
+            } else {
+                self.display
+            };
+            egl.MakeCurrent(display, self.draw, self.read, self.raw);
+
+            egl.BindAPI(self.previous_api);
+        }
+    }
+}
 
 pub(crate) struct ContextInner {
     display: Display,
-    config: Config,
-    raw: EglContext,
+    config: Option<Config>,
+    pub(crate) raw: EglContext,
+    shared_context: Option<EglContext>,
     api: egl::types::EGLenum,
+    robustness: Robustness,
+    label: Cell<Option<CString>>,
+    owned: bool,
 }
 
 impl ContextInner {
+    fn shared_context(&self) -> Option<RawContext> {
+        self.shared_context.as_ref().map(|shared_context| RawContext::Egl(**shared_context))
+    }
+
+    /// The config of the context, which is only missing for the contexts of
+    /// [`NoConfig`], so they don't implement [`GetGlConfig`].
+    fn config(&self) -> Config {
+        self.config.clone().expect("the config is present for the config contexts")
+    }
+
+    unsafe fn create_shared<C>(
+        &self,
+        context_attributes: &ContextAttributes,
+    ) -> Result<NotCurrentContext<C>> {
+        let mut context_attributes = context_attributes.clone();
+        context_attributes.shared_context = Some(RawContext::Egl(*self.raw));
+        unsafe {
+            self.display.create_context_with_config(self.config.as_ref(), &context_attributes)
+        }
+    }
+
+    fn current_surface(&self, readdraw: EGLenum) -> Option<EGLSurface> {
+        unsafe {
+            self.bind_api();
+            let egl = &self.display.inner.egl;
+            if egl.GetCurrentContext() != *self.raw {
+                return None;
+            }
+
+            let surface = egl.GetCurrentSurface(readdraw as EGLint);
+            (surface != egl::NO_SURFACE).then_some(surface)
+        }
+    }
+
     fn make_current_surfaceless(&self) -> Result<()> {
+        if !self.display.supports_surfaceless() {
+            return Err(
+                ErrorKind::NotSupported("EGL_KHR_surfaceless_context is not supported").into()
+            );
+        }
+
         unsafe {
             if self.display.inner.egl.MakeCurrent(
                 *self.display.inner.raw,
@@ -345,6 +951,30 @@ impl ContextInner {
         unsafe {
             let draw = surface_draw.raw;
             let read = surface_read.raw;
+
+            // Avoid hitting the driver when the same context and surfaces are
+            // already current.
+            self.bind_api();
+            let egl = &self.display.inner.egl;
+            if egl.GetCurrentContext() == *self.raw
+                && egl.GetCurrentSurface(egl::DRAW as EGLint) == draw
+                && egl.GetCurrentSurface(egl::READ as EGLint) == read
+            {
+                return Ok(());
+            }
+
+            // Check the configs upfront, since the driver reports the vague
+            // `EGL_BAD_MATCH` for them.
+            let draw_config = surface_draw.config();
+            let configs = [Some(surface_read.config()), self.config.clone()];
+            if configs
+                .into_iter()
+                .flatten()
+                .any(|config| !compatible_configs(&draw_config, &config))
+            {
+                return Err(ErrorKind::NotSupported("draw/read surface config mismatch").into());
+            }
+
             if self.display.inner.egl.MakeCurrent(*self.display.inner.raw, draw, read, *self.raw)
                 == egl::FALSE
             {
@@ -373,6 +1003,27 @@ impl ContextInner {
         }
     }
 
+    fn make_not_current_robust(&self) -> Result<()> {
+        match self.make_not_current() {
+            Err(err)
+                if matches!(
+                    err.error_kind(),
+                    ErrorKind::BadSurface | ErrorKind::BadCurrentSurface
+                ) =>
+            {
+                // Retrying the same call can't help, drop the dead surfaces
+                // first, so releasing the context doesn't touch them.
+                if !self.display.supports_surfaceless() {
+                    return Err(err);
+                }
+
+                self.make_current_surfaceless()?;
+                self.make_not_current()
+            },
+            result => result,
+        }
+    }
+
     fn context_api(&self) -> ContextApi {
         match self.query_attribute(egl::CONTEXT_CLIENT_TYPE as EGLint).map(|a| a as EGLenum) {
             Some(egl::OPENGL_API) => ContextApi::OpenGl(None),
@@ -381,6 +1032,200 @@ impl ContextInner {
         }
     }
 
+    fn priority(&self) -> Option<ContextPriority> {
+        if !self.display.inner.display_extensions.contains("EGL_IMG_context_priority") {
+            return None;
+        }
+
+        match self.query_attribute(egl::CONTEXT_PRIORITY_LEVEL_IMG as EGLint)? as EGLenum {
+            egl::CONTEXT_PRIORITY_LOW_IMG => Some(ContextPriority::Low),
+            egl::CONTEXT_PRIORITY_MEDIUM_IMG => Some(ContextPriority::Medium),
+            egl::CONTEXT_PRIORITY_HIGH_IMG => Some(ContextPriority::High),
+            _ => None,
+        }
+    }
+
+    fn reset_status(&self) -> Result<ResetStatus> {
+        if !matches!(
+            self.robustness,
+            Robustness::RobustLoseContextOnReset | Robustness::RobustNoResetNotification
+        ) {
+            return Err(ErrorKind::NotSupported("the context is not robust").into());
+        }
+
+        self.ensure_current()?;
+
+        // Core function is available since OpenGL 4.5 and OpenGL ES 3.2, use the
+        // extension variants otherwise.
+        let get_reset_status = self
+            .load_gl_fn(&[
+                b"glGetGraphicsResetStatus\0",
+                b"glGetGraphicsResetStatusKHR\0",
+                b"glGetGraphicsResetStatusARB\0",
+                b"glGetGraphicsResetStatusEXT\0",
+            ])
+            .ok_or(ErrorKind::NotSupported("glGetGraphicsResetStatus is not supported"))?;
+
+        let status = unsafe {
+            let get_reset_status: GlGetGraphicsResetStatus = mem::transmute(get_reset_status);
+            match get_reset_status() {
+                GL_GUILTY_CONTEXT_RESET => ResetStatus::GuiltyContextReset,
+                GL_INNOCENT_CONTEXT_RESET => ResetStatus::InnocentContextReset,
+                GL_UNKNOWN_CONTEXT_RESET => ResetStatus::UnknownContextReset,
+                _ => ResetStatus::NoError,
+            }
+        };
+
+        Ok(status)
+    }
+
+    /// Whether the surface with the given `config` could be made current with
+    /// the context.
+    pub(crate) fn is_compatible_with(&self, config: &Config) -> bool {
+        // The surface must be renderable with the Api of the context.
+        let api = if self.api == egl::OPENGL_API {
+            Api::OPENGL
+        } else {
+            Api::GLES1 | Api::GLES2 | Api::GLES3
+        };
+        if !config.api().intersects(api) {
+            return false;
+        }
+
+        match self.config.as_ref() {
+            Some(context_config) => compatible_configs(context_config, config),
+            // The config-less context could be used with any config of the
+            // display.
+            None => *config.display().inner.raw == *self.display.inner.raw,
+        }
+    }
+
+    /// Call the GL function without arguments, like `glFlush`.
+    fn call_gl_fn(&self, name: &'static [u8]) -> Result<()> {
+        self.ensure_current()?;
+
+        let gl_fn =
+            self.load_gl_fn(&[name]).ok_or(ErrorKind::NotSupported("GL function is not found"))?;
+
+        unsafe {
+            let gl_fn: GlVoidFn = mem::transmute(gl_fn);
+            gl_fn();
+        }
+
+        Ok(())
+    }
+
+    fn granted_robustness(&self) -> Result<Robustness> {
+        self.ensure_current()?;
+
+        // `GL_CONTEXT_FLAGS` is only available since OpenGL 3.0 and OpenGL ES 3.2,
+        // querying it on older contexts raises `GL_INVALID_ENUM`.
+        let version = self.context_version()?;
+        let is_gles = self.api == egl::OPENGL_ES_API;
+        let has_context_flags =
+            version >= if is_gles { Version::new(3, 2) } else { Version::new(3, 0) };
+        // The older OpenGL ES could still report the robust access with
+        // `GL_EXT_robustness`.
+        let has_ext_robustness = is_gles
+            && !has_context_flags
+            && matches!(
+                self.robustness,
+                Robustness::RobustLoseContextOnReset | Robustness::RobustNoResetNotification
+            )
+            && self
+                .gl_string(GL_EXTENSIONS)?
+                .split_ascii_whitespace()
+                .any(|extension| extension == "GL_EXT_robustness");
+        if !has_context_flags && !has_ext_robustness {
+            return Ok(self.robustness);
+        }
+
+        let get_integer = self
+            .load_gl_fn(&[b"glGetIntegerv\0"])
+            .ok_or(ErrorKind::NotSupported("glGetIntegerv is not supported"))?;
+
+        unsafe {
+            let get_integer: GlGetIntegerv = mem::transmute(get_integer);
+            let get_integer = |name| {
+                let mut value = 0;
+                get_integer(name, &mut value);
+                value
+            };
+
+            let flags = if has_context_flags { get_integer(GL_CONTEXT_FLAGS) } else { 0 };
+            if flags & GL_CONTEXT_FLAG_NO_ERROR_BIT != 0 {
+                return Ok(Robustness::NoError);
+            }
+
+            // Only query the robust access when it was requested, since the
+            // query is not supported by the contexts without robustness
+            // extensions.
+            let robust = self.robustness != Robustness::NotRobust
+                && self.robustness != Robustness::NoError
+                && (flags & GL_CONTEXT_FLAG_ROBUST_ACCESS_BIT != 0
+                    || (is_gles && get_integer(GL_CONTEXT_ROBUST_ACCESS) != 0));
+            if !robust {
+                return Ok(Robustness::NotRobust);
+            }
+
+            if get_integer(GL_RESET_NOTIFICATION_STRATEGY) == GL_LOSE_CONTEXT_ON_RESET {
+                Ok(Robustness::RobustLoseContextOnReset)
+            } else {
+                Ok(Robustness::RobustNoResetNotification)
+            }
+        }
+    }
+
+    fn context_version(&self) -> Result<Version> {
+        parse_gl_version(&self.gl_string(GL_VERSION)?)
+            .ok_or_else(|| ErrorKind::NotSupported("failed to parse GL_VERSION").into())
+    }
+
+    /// Query the GL string with `glGetString`.
+    fn gl_string(&self, name: u32) -> Result<String> {
+        self.ensure_current()?;
+
+        let get_string = self
+            .load_gl_fn(&[b"glGetString\0"])
+            .ok_or(ErrorKind::NotSupported("glGetString is not supported"))?;
+
+        unsafe {
+            let get_string: GlGetString = mem::transmute(get_string);
+            let string = get_string(name);
+            if string.is_null() {
+                return Err(ErrorKind::BadContextState.into());
+            }
+
+            Ok(CStr::from_ptr(string as *const _).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Ensure that the context is current on the calling thread, since the GL
+    /// functions operate on the current context.
+    fn ensure_current(&self) -> Result<()> {
+        unsafe {
+            self.bind_api();
+            if self.display.inner.egl.GetCurrentContext() == *self.raw {
+                Ok(())
+            } else {
+                Err(ErrorKind::BadContextState.into())
+            }
+        }
+    }
+
+    /// Load the GL function trying the null terminated `names` in order.
+    ///
+    /// The lookup goes through [`GlDisplay::get_proc_address`], so the core
+    /// functions not returned by `eglGetProcAddress` are still found in the GL
+    /// library.
+    fn load_gl_fn(&self, names: &[&[u8]]) -> Option<*const ffi::c_void> {
+        names
+            .iter()
+            .filter_map(|name| CStr::from_bytes_with_nul(name).ok())
+            .map(|name| self.display.get_proc_address(name))
+            .find(|ptr| !ptr.is_null())
+    }
+
     /// Query the context attribute.
     fn query_attribute(&self, attribute: EGLint) -> Option<EGLint> {
         unsafe {
@@ -418,8 +1263,105 @@ impl ContextInner {
     }
 }
 
+/// Push the robustness, debug and context flags attributes used with EGL 1.5
+/// and `EGL_KHR_create_context`, returning the robustness requested from the
+/// driver.
+///
+/// EGL 1.4 uses the debug context flag and the
+/// `EGL_EXT_create_context_robustness` tokens instead of the core ones.
+fn push_khr_context_attributes(
+    attrs: &mut Vec<EGLint>,
+    version: Version,
+    features: DisplayFeatures,
+    context_attributes: &ContextAttributes,
+    mut flags: EGLint,
+) -> Result<Robustness> {
+    let mut robustness = Robustness::NotRobust;
+    let is_one_five = version >= Version::new(1, 5);
+    let has_robustsess = features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS);
+
+    let mut requested_no_error = false;
+    match context_attributes.robustness {
+        Robustness::NotRobust => (),
+        Robustness::NoError if features.contains(DisplayFeatures::CONTEXT_NO_ERROR) => {
+            attrs.push(egl::CONTEXT_OPENGL_NO_ERROR_KHR as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+            requested_no_error = true;
+            robustness = Robustness::NoError;
+        },
+        Robustness::NoError => {
+            return Err(
+                ErrorKind::NotSupported("EGL_KHR_create_context_no_error is not supported").into()
+            )
+        },
+        Robustness::RobustLoseContextOnReset if has_robustsess && is_one_five => {
+            attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
+            attrs.push(egl::LOSE_CONTEXT_ON_RESET as EGLint);
+            flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR as EGLint;
+            robustness = Robustness::RobustLoseContextOnReset;
+        },
+        Robustness::RobustNoResetNotification if has_robustsess && is_one_five => {
+            attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
+            attrs.push(egl::NO_RESET_NOTIFICATION as EGLint);
+            flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR as EGLint;
+            robustness = Robustness::RobustNoResetNotification;
+        },
+        // EGL 1.4 only has robustness with `EGL_EXT_create_context_robustness`.
+        robust @ (Robustness::RobustLoseContextOnReset | Robustness::RobustNoResetNotification)
+            if has_robustsess =>
+        {
+            push_ext_robustness(attrs, robust);
+            robustness = robust;
+        },
+        _ => return Err(ErrorKind::NotSupported("context robustness is not supported").into()),
+    }
+
+    if context_attributes.debug && !requested_no_error {
+        if is_one_five {
+            attrs.push(egl::CONTEXT_OPENGL_DEBUG as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        } else {
+            // `EGL_KHR_create_context` uses the context flag instead.
+            flags |= egl::CONTEXT_OPENGL_DEBUG_BIT_KHR as EGLint;
+        }
+    }
+
+    if flags != 0 {
+        attrs.push(egl::CONTEXT_FLAGS_KHR as EGLint);
+        attrs.push(flags);
+    }
+
+    Ok(robustness)
+}
+
+/// Push the `EGL_EXT_create_context_robustness` attributes, which use
+/// different tokens than EGL 1.5.
+fn push_ext_robustness(attrs: &mut Vec<EGLint>, robustness: Robustness) {
+    let strategy = match robustness {
+        Robustness::RobustLoseContextOnReset => egl::LOSE_CONTEXT_ON_RESET_EXT,
+        _ => egl::NO_RESET_NOTIFICATION_EXT,
+    };
+
+    attrs.push(egl::CONTEXT_OPENGL_ROBUST_ACCESS_EXT as EGLint);
+    attrs.push(egl::TRUE as EGLint);
+    attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY_EXT as EGLint);
+    attrs.push(strategy as EGLint);
+}
+
+/// Whether the configs have the same color and ancillary buffers, which is
+/// how EGL defines the compatible configs.
+fn compatible_configs(lhs: &Config, rhs: &Config) -> bool {
+    // Compare the handles first, since comparing the attributes queries the
+    // driver.
+    *lhs.inner.raw == *rhs.inner.raw || lhs.pixel_format() == rhs.pixel_format()
+}
+
 impl Drop for ContextInner {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
         unsafe {
             self.display.inner.egl.DestroyContext(*self.display.inner.raw, *self.raw);
         }
@@ -430,7 +1372,7 @@ impl fmt::Debug for ContextInner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Context")
             .field("display", &self.display.inner.raw)
-            .field("config", &self.config.inner.raw)
+            .field("config", &self.config.as_ref().map(|config| &config.inner.raw))
             .field("raw", &self.raw)
             .finish()
     }
@@ -449,3 +1391,130 @@ impl Deref for EglContext {
         &self.0
     }
 }
+
+/// Parse the `major.minor` version out of the `GL_VERSION` string.
+///
+/// The string is `<major>.<minor>[.<release>] [<vendor info>]` for OpenGL and
+/// `OpenGL ES <major>.<minor> [<vendor info>]` for OpenGL ES.
+fn parse_gl_version(version: &str) -> Option<Version> {
+    let version = version.strip_prefix("OpenGL ES").map_or(version, |version| {
+        // Skip the `-CM`/`-CL` profile suffix of OpenGL ES 1.x.
+        version.trim_start_matches(|c: char| c != ' ').trim_start()
+    });
+
+    let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next()?.parse().ok()?;
+    Some(Version::new(major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+    use crate::context::ContextAttributesBuilder;
+    use crate::surface::{PbufferSurface, SurfaceAttributesBuilder};
+
+    fn khr_attributes(version: Version, robustness: Robustness, debug: bool) -> Vec<EGLint> {
+        let context_attributes = ContextAttributesBuilder::new()
+            .with_robustness(robustness)
+            .with_debug(debug)
+            .build(None);
+        let features = DisplayFeatures::CONTEXT_ROBUSTNESS;
+
+        let mut attrs = Vec::new();
+        push_khr_context_attributes(&mut attrs, version, features, &context_attributes, 0).unwrap();
+        attrs
+    }
+
+    #[test]
+    fn debug_flag_before_egl_1_5() {
+        let attrs = khr_attributes(Version::new(1, 4), Robustness::NotRobust, true);
+        assert_eq!(
+            attrs,
+            [egl::CONTEXT_FLAGS_KHR as EGLint, egl::CONTEXT_OPENGL_DEBUG_BIT_KHR as EGLint]
+        );
+    }
+
+    #[test]
+    fn debug_attribute_since_egl_1_5() {
+        let attrs = khr_attributes(Version::new(1, 5), Robustness::NotRobust, true);
+        assert_eq!(attrs, [egl::CONTEXT_OPENGL_DEBUG as EGLint, egl::TRUE as EGLint]);
+    }
+
+    #[test]
+    #[ignore = "requires an EGL device"]
+    fn make_current_with_different_surfaces() {
+        let display = super::super::test_display().expect("no EGL device");
+
+        // Pbuffers stand in for the window surfaces, which need a window system.
+        let template =
+            ConfigTemplateBuilder::new().with_surface_type(ConfigSurfaceTypes::PBUFFER).build();
+        let config =
+            unsafe { display.find_configs(template) }.unwrap().next().expect("no pbuffer config");
+
+        let size = NonZeroU32::new(16).unwrap();
+        let attributes = SurfaceAttributesBuilder::<PbufferSurface>::new().build(size, size);
+        let surfaces = [(); 2]
+            .map(|_| unsafe { display.create_pbuffer_surface(&config, &attributes) }.unwrap());
+
+        let context_attributes = ContextAttributesBuilder::new().build(None);
+        let context = unsafe { display.create_context(&config, &context_attributes) }
+            .unwrap()
+            .treat_as_possibly_current();
+
+        // Alternate between the surfaces with the same context.
+        for surface in surfaces.iter().chain(surfaces.iter()) {
+            assert!(surface.is_compatible_with(&context));
+            context.make_current(surface).unwrap();
+            assert_eq!(context.current_draw_surface(), Some(surface.raw));
+        }
+
+        context.make_not_current().unwrap();
+    }
+
+    #[test]
+    fn ext_robustness_before_egl_1_5() {
+        let attrs = khr_attributes(Version::new(1, 4), Robustness::RobustLoseContextOnReset, false);
+        assert_eq!(
+            attrs,
+            [
+                egl::CONTEXT_OPENGL_ROBUST_ACCESS_EXT as EGLint,
+                egl::TRUE as EGLint,
+                egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY_EXT as EGLint,
+                egl::LOSE_CONTEXT_ON_RESET_EXT as EGLint,
+            ]
+        );
+    }
+
+    #[test]
+    fn core_robustness_since_egl_1_5() {
+        let attrs = khr_attributes(Version::new(1, 5), Robustness::RobustLoseContextOnReset, false);
+        assert_eq!(
+            attrs,
+            [
+                egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint,
+                egl::LOSE_CONTEXT_ON_RESET as EGLint,
+                egl::CONTEXT_FLAGS_KHR as EGLint,
+                egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR as EGLint,
+            ]
+        );
+    }
+
+    #[test]
+    fn ext_robustness_without_create_context() {
+        let mut attrs = Vec::new();
+        push_ext_robustness(&mut attrs, Robustness::RobustNoResetNotification);
+        assert_eq!(
+            attrs,
+            [
+                egl::CONTEXT_OPENGL_ROBUST_ACCESS_EXT as EGLint,
+                egl::TRUE as EGLint,
+                egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY_EXT as EGLint,
+                egl::NO_RESET_NOTIFICATION_EXT as EGLint,
+            ]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Everything related to `EGL_KHR_debug`.
+
+use std::cell::Cell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLLabelKHR, EGLObjectKHR, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::{Display, CLIENT_EXTENSIONS};
+
+type DebugCallback = dyn Fn(DebugMessage<'_>) + Send + Sync;
+
+/// The user callback receiving the messages.
+///
+/// The callback is global, since `eglDebugMessageControlKHR` is not tied to
+/// any display. It's reference counted, so it could be called without holding
+/// the lock.
+static DEBUG_CALLBACK: Mutex<Option<Arc<DebugCallback>>> = Mutex::new(None);
+
+impl Display {
+    /// Register the `callback` receiving debug messages from the EGL
+    /// implementation, replacing the previously registered one.
+    ///
+    /// The callback is process wide and could be called from any thread. The
+    /// labels set with `set_label` on the glutin objects are passed back in
+    /// [`DebugMessage::object_label`].
+    ///
+    /// This requires `EGL_KHR_debug`.
+    pub fn enable_debug_callback<F>(&self, callback: F) -> Result<()>
+    where
+        F: Fn(DebugMessage<'_>) + Send + Sync + 'static,
+    {
+        if !CLIENT_EXTENSIONS.get().unwrap().contains("EGL_KHR_debug") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_debug is not supported").into());
+        }
+
+        *DEBUG_CALLBACK.lock().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(callback));
+
+        let attrs = [
+            egl::DEBUG_MSG_CRITICAL_KHR as EGLAttrib,
+            egl::TRUE as EGLAttrib,
+            egl::DEBUG_MSG_ERROR_KHR as EGLAttrib,
+            egl::TRUE as EGLAttrib,
+            egl::DEBUG_MSG_WARN_KHR as EGLAttrib,
+            egl::TRUE as EGLAttrib,
+            egl::DEBUG_MSG_INFO_KHR as EGLAttrib,
+            egl::TRUE as EGLAttrib,
+            egl::NONE as EGLAttrib,
+        ];
+
+        let result =
+            unsafe { self.inner.egl.DebugMessageControlKHR(Some(debug_callback), attrs.as_ptr()) };
+        super::error_from_code(result as EGLenum)
+    }
+}
+
+/// The severity of the [`DebugMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugSeverity {
+    /// The operation caused an unrecoverable error, like a lost context.
+    Critical,
+
+    /// The operation generated an EGL error.
+    Error,
+
+    /// The operation succeeded, but the implementation detected a potential
+    /// issue.
+    Warning,
+
+    /// The informational message.
+    Info,
+}
+
+/// The message passed to the callback registered with
+/// [`Display::enable_debug_callback`].
+#[derive(Debug, Clone, Copy)]
+pub struct DebugMessage<'a> {
+    /// The severity of the message.
+    pub severity: DebugSeverity,
+
+    /// The error generated by the command, if any.
+    pub error: Option<ErrorKind>,
+
+    /// The name of the EGL command which generated the message.
+    pub command: &'a str,
+
+    /// The label of the object the message is about.
+    pub object_label: Option<&'a str>,
+
+    /// The message from the implementation.
+    pub message: &'a str,
+}
+
+/// Attach the `label` to the EGL `object`, keeping the label alive in
+/// `storage`.
+pub(crate) fn label_object(
+    display: &Display,
+    object_type: EGLenum,
+    object: EGLObjectKHR,
+    storage: &Cell<Option<CString>>,
+    label: &str,
+) -> Result<()> {
+    if !CLIENT_EXTENSIONS.get().unwrap().contains("EGL_KHR_debug") {
+        return Err(ErrorKind::NotSupported("EGL_KHR_debug is not supported").into());
+    }
+
+    let label = CString::new(label).map_err(|_| ErrorKind::BadParameter)?;
+    let result = unsafe {
+        display.inner.egl.LabelObjectKHR(
+            *display.inner.raw,
+            object_type,
+            object,
+            label.as_ptr() as EGLLabelKHR,
+        )
+    };
+    super::error_from_code(result as EGLenum)?;
+
+    // The old label is no longer referenced by EGL, so it could be dropped.
+    storage.set(Some(label));
+
+    Ok(())
+}
+
+extern "system" fn debug_callback(
+    error: EGLenum,
+    command: *const c_char,
+    message_type: EGLint,
+    _thread_label: EGLLabelKHR,
+    object_label: EGLLabelKHR,
+    message: *const c_char,
+) {
+    let severity = match message_type as EGLenum {
+        egl::DEBUG_MSG_CRITICAL_KHR => DebugSeverity::Critical,
+        egl::DEBUG_MSG_ERROR_KHR => DebugSeverity::Error,
+        egl::DEBUG_MSG_WARN_KHR => DebugSeverity::Warning,
+        _ => DebugSeverity::Info,
+    };
+
+    let to_str = |ptr: *const c_char| {
+        (!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }.to_str().ok()).flatten()
+    };
+
+    let message = DebugMessage {
+        severity,
+        error: super::error_from_code(error).err().map(|err| err.error_kind()),
+        command: to_str(command).unwrap_or_default(),
+        object_label: to_str(object_label as *const c_char),
+        message: to_str(message).unwrap_or_default(),
+    };
+
+    // Release the lock before calling the callback, since it could make EGL
+    // calls generating messages or replace the callback.
+    let callback = DEBUG_CALLBACK.lock().unwrap_or_else(|err| err.into_inner()).clone();
+    if let Some(callback) = callback {
+        // Don't unwind into the EGL implementation.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(message)));
+    }
+}
@@ -19,6 +19,8 @@ pub struct Device {
     extensions: HashSet<&'static str>,
     name: Option<String>,
     vendor: Option<String>,
+    drm_device_node_path: Option<String>,
+    drm_render_device_node_path: Option<String>,
 }
 
 impl Device {
@@ -41,9 +43,9 @@ impl Device {
         //
         // Or we can check for the EGL_EXT_device_base extension since it contains both
         // extensions.
-        if (!no_display_extensions.contains("EGL_EXT_device_enumeration")
-            && !no_display_extensions.contains("EGL_EXT_device_query"))
-            || !no_display_extensions.contains("EGL_EXT_device_base")
+        if !no_display_extensions.contains("EGL_EXT_device_base")
+            && (!no_display_extensions.contains("EGL_EXT_device_enumeration")
+                || !no_display_extensions.contains("EGL_EXT_device_query"))
         {
             return Err(ErrorKind::NotSupported("EGL does not support EGL_EXT_device_base").into());
         }
@@ -107,6 +109,23 @@ impl Device {
         self.vendor.as_deref()
     }
 
+    /// Get the DRM device node path of the device, like `/dev/dri/card0`.
+    ///
+    /// This function will return [`None`] if the `EGL_EXT_device_drm` device
+    /// extension is not available.
+    pub fn drm_device_node_path(&self) -> Option<&str> {
+        self.drm_device_node_path.as_deref()
+    }
+
+    /// Get the DRM render node path of the device, like
+    /// `/dev/dri/renderD128`.
+    ///
+    /// This function will return [`None`] if the
+    /// `EGL_EXT_device_drm_render_node` device extension is not available.
+    pub fn drm_render_device_node_path(&self) -> Option<&str> {
+        self.drm_render_device_node_path.as_deref()
+    }
+
     /// Get a raw handle to the `EGLDevice`.
     pub fn raw_device(&self) -> *const c_void {
         self.inner
@@ -152,6 +171,29 @@ impl Device {
             (None, None)
         };
 
-        Ok(Self { inner: ptr, extensions, name, vendor })
+        let drm_device_node_path = if extensions.contains("EGL_EXT_device_drm") {
+            // SAFETY: DRM_DEVICE_FILE_EXT is a valid string for device string queries if
+            // EGL_EXT_device_drm.
+            unsafe { Self::query_string(ptr, egl::DRM_DEVICE_FILE_EXT) }
+        } else {
+            None
+        };
+
+        let drm_render_device_node_path = if extensions.contains("EGL_EXT_device_drm_render_node") {
+            // SAFETY: DRM_RENDER_NODE_FILE_EXT is a valid string for device string queries
+            // if EGL_EXT_device_drm_render_node.
+            unsafe { Self::query_string(ptr, egl::DRM_RENDER_NODE_FILE_EXT) }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            inner: ptr,
+            extensions,
+            name,
+            vendor,
+            drm_device_node_path,
+            drm_render_device_node_path,
+        })
     }
 }
@@ -2,21 +2,26 @@
 
 use std::collections::HashSet;
 use std::ffi::{self, CStr};
+#[cfg(not(feature = "thread-safe-display"))]
+use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::c_char;
 use std::sync::Arc;
-use std::{fmt, ptr};
+#[cfg(feature = "thread-safe-display")]
+use std::sync::{Mutex, MutexGuard};
+use std::{fmt, mem, ptr};
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLAttrib, EGLDisplay, EGLint};
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLDisplay, EGLenum, EGLint};
 
+use libloading::Library;
 use once_cell::sync::OnceCell;
 
-use raw_window_handle::RawDisplayHandle;
+use raw_window_handle::{GbmDisplayHandle, RawDisplayHandle};
 
 use crate::config::ConfigTemplate;
-use crate::context::Version;
+use crate::context::{ContextApi, RawContext, Version};
 use crate::display::{AsRawDisplay, DisplayFeatures, GetDisplayExtensions, RawDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
@@ -30,6 +35,13 @@ use super::surface::Surface;
 
 use super::{Egl, EGL};
 
+type EglGetDisplayDriverName = unsafe extern "system" fn(EGLDisplay) -> *const c_char;
+type EglGetDisplayDriverConfig = unsafe extern "system" fn(EGLDisplay) -> *mut c_char;
+
+extern "C" {
+    fn free(ptr: *mut ffi::c_void);
+}
+
 /// Extensions that don't require any display.
 pub(crate) static CLIENT_EXTENSIONS: OnceCell<HashSet<&'static str>> = OnceCell::new();
 
@@ -41,6 +53,9 @@ pub struct Display {
 }
 
 impl Display {
+    /// The native rendering engine of the platform.
+    pub const CORE_NATIVE_ENGINE: i32 = egl::CORE_NATIVE_ENGINE as i32;
+
     /// Create EGL display with the native display.
     ///
     /// # Safety
@@ -55,6 +70,36 @@ impl Display {
             None => return Err(ErrorKind::NotFound.into()),
         };
 
+        Self::new_with_egl(egl, raw_display)
+    }
+
+    /// Create EGL display with the native display, resolving the EGL
+    /// functions with the `loader` instead of loading the system `libEGL`.
+    ///
+    /// This is useful when `libEGL` lives at a non standard path, for example
+    /// in sandboxed environments, or to test against the mock EGL.
+    ///
+    /// The loader is process wide and the first caller wins: it must be
+    /// provided before any other EGL usage and it's used for all the displays
+    /// created afterwards, including with [`Display::new`]. Passing the same
+    /// loader again, that is resolving the same `eglGetProcAddress`, is
+    /// accepted. [`ErrorKind::NotSupported`] is returned for the different
+    /// loader or when EGL was already loaded from the system library, in which
+    /// case the loader is not kept.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`], and the `loader` must return valid
+    /// EGL functions or null.
+    pub unsafe fn new_with_loader<F>(raw_display: RawDisplayHandle, loader: F) -> Result<Self>
+    where
+        F: Fn(&CStr) -> *const ffi::c_void + Send + Sync + 'static,
+    {
+        let egl = super::load_with_loader(Box::new(loader))?;
+        Self::new_with_egl(egl, raw_display)
+    }
+
+    fn new_with_egl(egl: &'static Egl, raw_display: RawDisplayHandle) -> Result<Self> {
         CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
 
         // Create a EGL display by chaining all display creation functions aborting on
@@ -78,6 +123,101 @@ impl Display {
         Self::initialize_display(egl, display, Some(raw_display))
     }
 
+    /// Create EGL display with the native display on the explicitly
+    /// requested `platform`.
+    ///
+    /// Unlike [`Display::new`], this function never falls back to
+    /// `eglGetDisplay`, which lets the implementation guess the platform, so
+    /// it could be used when several EGL platforms are installed. The
+    /// `raw_display` must match the `platform`, and it's ignored for
+    /// [`Platform::Surfaceless`]. To use the device platform see
+    /// [`Display::with_device`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn new_with_platform(
+        raw_display: RawDisplayHandle,
+        platform: Platform,
+    ) -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        let matches_platform = match platform {
+            Platform::Wayland => matches!(raw_display, RawDisplayHandle::Wayland(_)),
+            Platform::X11 => {
+                matches!(raw_display, RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_))
+            },
+            Platform::Gbm => matches!(raw_display, RawDisplayHandle::Gbm(_)),
+            Platform::Surfaceless => {
+                let display = Self::get_surfaceless_display(egl)?;
+                return Self::initialize_display(egl, display, None);
+            },
+        };
+
+        if !matches_platform {
+            return Err(ErrorKind::NotSupported(
+                "provided display handle doesn't match the platform",
+            )
+            .into());
+        }
+
+        let display = Self::get_platform_display(egl, raw_display).or_else(|err| {
+            if err.error_kind() == ErrorKind::BadAttribute {
+                Err(err)
+            } else {
+                Self::get_platform_display_ext(egl, raw_display)
+            }
+        })?;
+
+        Self::initialize_display(egl, display, Some(raw_display))
+    }
+
+    /// Create EGL display with the native display, failing when the
+    /// implementation reports the EGL version lower than `requested`.
+    ///
+    /// This is useful when targeting the feature set of the particular EGL
+    /// version, since the version is checked once on creation. The
+    /// negotiated version is available with [`Display::version`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn new_with_version(
+        raw_display: RawDisplayHandle,
+        requested: Version,
+    ) -> Result<Self> {
+        let display = unsafe { Self::new(raw_display)? };
+        if display.inner.version < requested {
+            return Err(
+                ErrorKind::NotSupported("the EGL version is lower than the requested one").into()
+            );
+        }
+
+        Ok(display)
+    }
+
+    /// Create EGL display on the GBM platform from the `gbm_device`, which is
+    /// used for rendering on DRM without X11 or Wayland.
+    ///
+    /// Window surfaces are created from `gbm_surface` passed with
+    /// [`RawWindowHandle::Gbm`].
+    ///
+    /// # Safety
+    ///
+    /// `gbm_device` must point to a valid `gbm_device` outliving the display.
+    ///
+    /// [`RawWindowHandle::Gbm`]: raw_window_handle::RawWindowHandle::Gbm
+    pub unsafe fn new_from_gbm(gbm_device: *mut ffi::c_void) -> Result<Self> {
+        let mut handle = GbmDisplayHandle::empty();
+        handle.gbm_device = gbm_device;
+        unsafe { Self::new_with_platform(RawDisplayHandle::Gbm(handle), Platform::Gbm) }
+    }
+
     /// Create an EGL display using the specified device.
     ///
     /// In most cases, prefer [`Display::new()`] unless you need to render
@@ -212,6 +352,156 @@ impl Display {
         Device::from_ptr(self.inner.egl, device)
     }
 
+    /// The EGL version negotiated with `eglInitialize`.
+    pub fn version(&self) -> Version {
+        self.inner.version
+    }
+
+    /// The vendor of the EGL implementation from `EGL_VENDOR`.
+    ///
+    /// The display extensions, like `EGL_KHR_image_base`, are available with
+    /// [`GetDisplayExtensions::extensions`].
+    pub fn vendor(&self) -> Option<&'static str> {
+        self.query_string(egl::VENDOR)
+    }
+
+    /// The version string of the EGL implementation from `EGL_VERSION`,
+    /// which usually includes vendor specific information.
+    pub fn version_info(&self) -> Option<&'static str> {
+        self.query_string(egl::VERSION)
+    }
+
+    /// Release the EGL state of the calling thread with `eglReleaseThread`.
+    ///
+    /// The drivers keep the per-thread resources until the thread exits, so
+    /// the long lived thread pools should call this once the thread is done
+    /// with EGL. It must be called after the context current on the calling
+    /// thread was made not current with
+    /// [`PossiblyCurrentGlContext::make_not_current`], otherwise the context
+    /// is released implicitly.
+    ///
+    /// [`PossiblyCurrentGlContext::make_not_current`]: crate::context::PossiblyCurrentGlContext::make_not_current
+    pub fn release_thread(&self) -> Result<()> {
+        unsafe {
+            if self.inner.egl.ReleaseThread() == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Check whether the display is still usable.
+    ///
+    /// Some platforms invalidate the display when the device backing it is
+    /// gone, like when the external GPU is unplugged, which makes the EGL
+    /// calls fail with `EGL_BAD_DISPLAY` or `EGL_NOT_INITIALIZED`. In such
+    /// case all the objects created with the display must be dropped and the
+    /// display recreated. The debug callback from
+    /// [`Self::enable_debug_callback`] reports such errors as well.
+    pub fn check_alive(&self) -> bool {
+        let mut num_configs = 0;
+        unsafe {
+            if self.inner.egl.GetConfigs(*self.inner.raw, ptr::null_mut(), 0, &mut num_configs)
+                != egl::FALSE
+            {
+                return true;
+            }
+
+            !matches!(self.inner.egl.GetError() as EGLenum, egl::BAD_DISPLAY | egl::NOT_INITIALIZED)
+        }
+    }
+
+    /// The context current on the calling thread with `eglGetCurrentContext`,
+    /// when it belongs to this display.
+    ///
+    /// This is useful for the code not owning the context, which needs to
+    /// save and restore the current context. The context is looked up for
+    /// the Api returned by [`Self::bound_api`].
+    pub fn current_context(&self) -> Option<RawContext> {
+        unsafe {
+            if self.inner.egl.GetCurrentDisplay() != *self.inner.raw {
+                return None;
+            }
+
+            let context = self.inner.egl.GetCurrentContext();
+            (context != egl::NO_CONTEXT).then_some(RawContext::Egl(context))
+        }
+    }
+
+    /// The display current on the calling thread with `eglGetCurrentDisplay`,
+    /// which is not necessarily this display.
+    pub fn current_display(&self) -> Option<RawDisplay> {
+        let display = unsafe { self.inner.egl.GetCurrentDisplay() };
+        (display != egl::NO_DISPLAY).then_some(RawDisplay::Egl(display))
+    }
+
+    /// The rendering Api bound on the calling thread with `eglBindAPI`.
+    ///
+    /// The returned value's [`Version`] will always be `None`, and `None` is
+    /// returned when the OpenVG Api is bound.
+    pub fn bound_api(&self) -> Option<ContextApi> {
+        match unsafe { self.inner.egl.QueryAPI() } {
+            egl::OPENGL_API => Some(ContextApi::OpenGl(None)),
+            egl::OPENGL_ES_API => Some(ContextApi::Gles(None)),
+            _ => None,
+        }
+    }
+
+    /// Whether contexts could be made current without any surfaces with
+    /// `EGL_KHR_surfaceless_context`.
+    pub fn supports_surfaceless(&self) -> bool {
+        self.inner.display_extensions.contains("EGL_KHR_surfaceless_context")
+    }
+
+    /// Whether robust contexts could be created.
+    pub fn supports_robustness(&self) -> bool {
+        self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS)
+    }
+
+    /// Whether [`Robustness::NoError`] contexts could be created.
+    ///
+    /// [`Robustness::NoError`]: crate::context::Robustness::NoError
+    pub fn supports_no_error(&self) -> bool {
+        self.inner.features.contains(DisplayFeatures::CONTEXT_NO_ERROR)
+    }
+
+    /// Whether the context priority could be requested with
+    /// `EGL_IMG_context_priority`.
+    pub fn supports_context_priority(&self) -> bool {
+        self.inner.display_extensions.contains("EGL_IMG_context_priority")
+    }
+
+    /// Wait for the client Api rendering of the current context to complete
+    /// before doing native rendering with `eglWaitClient`.
+    ///
+    /// This function does nothing when there's no current context on the
+    /// calling thread.
+    pub fn wait_client(&self) -> Result<()> {
+        unsafe {
+            if self.inner.egl.WaitClient() == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Wait for the native rendering of the given `engine` to complete before
+    /// doing client Api rendering with `eglWaitNative`.
+    ///
+    /// The `engine` is usually `EGL_CORE_NATIVE_ENGINE`, which is exposed as
+    /// [`Self::CORE_NATIVE_ENGINE`].
+    pub fn wait_native(&self, engine: i32) -> Result<()> {
+        unsafe {
+            if self.inner.egl.WaitNative(engine as EGLint) == egl::FALSE {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
     /// Terminate the EGL display.
     ///
     /// When the display is managed by glutin with the
@@ -403,6 +693,128 @@ impl Display {
         })
     }
 
+    pub(crate) fn get_proc_addresses(&self, names: &[&CStr]) -> Vec<*const ffi::c_void> {
+        let egl = &self.inner.egl;
+        names
+            .iter()
+            .map(|name| {
+                let proc_address = unsafe { egl.GetProcAddress(name.as_ptr()) as *const _ };
+                if proc_address.is_null() {
+                    self.gl_library_symbol(name)
+                } else {
+                    proc_address
+                }
+            })
+            .collect()
+    }
+
+    /// Lookup the symbol in the GL library of the bound Api, loading it on the
+    /// first call.
+    fn gl_library_symbol(&self, name: &CStr) -> *const ffi::c_void {
+        let api = unsafe { self.inner.egl.QueryAPI() };
+        let gl_library =
+            if api == egl::OPENGL_API { &self.inner.gl_library } else { &self.inner.gles_library };
+
+        gl_library
+            .get_or_init(|| load_gl_library(api))
+            .as_ref()
+            .and_then(|lib| unsafe { lib.get::<*const ffi::c_void>(name.to_bytes_with_nul()).ok() })
+            .map_or(ptr::null(), |sym| *sym)
+    }
+
+    /// The name of the driver from `EGL_MESA_query_driver`, like `iris`,
+    /// `radeonsi` or `zink`.
+    ///
+    /// `None` is returned when the extension is not supported.
+    pub fn driver_name(&self) -> Option<String> {
+        let get_driver_name = self.load_mesa_query_driver_fn(b"eglGetDisplayDriverName\0")?;
+        unsafe {
+            let get_driver_name: EglGetDisplayDriverName = mem::transmute(get_driver_name);
+            let name = get_driver_name(*self.inner.raw);
+            if name.is_null() {
+                return None;
+            }
+
+            Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    }
+
+    /// The XML describing the driver configuration options from
+    /// `EGL_MESA_query_driver`, which is useful for bug reports.
+    ///
+    /// `None` is returned when the extension is not supported.
+    pub fn driver_config(&self) -> Option<String> {
+        let get_driver_config = self.load_mesa_query_driver_fn(b"eglGetDisplayDriverConfig\0")?;
+        unsafe {
+            let get_driver_config: EglGetDisplayDriverConfig = mem::transmute(get_driver_config);
+            let config = get_driver_config(*self.inner.raw);
+            if config.is_null() {
+                return None;
+            }
+
+            let result = CStr::from_ptr(config).to_string_lossy().into_owned();
+            // The string is allocated by the driver and must be freed by the caller.
+            free(config.cast());
+            Some(result)
+        }
+    }
+
+    /// Whether the display is backed by the hardware driver, based on
+    /// [`Self::driver_name`].
+    ///
+    /// `None` is returned when the driver can't be identified, use
+    /// [`PossiblyCurrentContext::is_hardware_accelerated`] which inspects
+    /// `GL_RENDERER` instead.
+    ///
+    /// [`PossiblyCurrentContext::is_hardware_accelerated`]: crate::api::egl::context::PossiblyCurrentContext::is_hardware_accelerated
+    pub fn is_hardware_accelerated(&self) -> Option<bool> {
+        self.driver_name().map(|name| !is_software_renderer(&name))
+    }
+
+    fn load_mesa_query_driver_fn(&self, name: &[u8]) -> Option<*const ffi::c_void> {
+        if !self.inner.display_extensions.contains("EGL_MESA_query_driver") {
+            return None;
+        }
+
+        let proc_address =
+            unsafe { self.inner.egl.GetProcAddress(name.as_ptr() as *const _) as *const _ };
+        (!proc_address.is_null()).then_some(proc_address)
+    }
+
+    fn query_string(&self, name: EGLenum) -> Option<&'static str> {
+        unsafe {
+            let string = self.inner.egl.QueryString(*self.inner.raw, name as EGLint);
+            if string.is_null() {
+                return None;
+            }
+
+            // SAFETY: The EGL specification guarantees the returned string is
+            // static and null terminated.
+            CStr::from_ptr(string).to_str().ok()
+        }
+    }
+
+    fn get_surfaceless_display(egl: &Egl) -> Result<EglDisplay> {
+        let extensions = CLIENT_EXTENSIONS.get().unwrap();
+        if !egl.GetPlatformDisplayEXT.is_loaded()
+            || !extensions.contains("EGL_MESA_platform_surfaceless")
+        {
+            return Err(
+                ErrorKind::NotSupported("EGL_MESA_platform_surfaceless is not supported").into()
+            );
+        }
+
+        let attrs = [egl::NONE as EGLint];
+        Self::check_display_error(unsafe {
+            egl.GetPlatformDisplayEXT(
+                egl::PLATFORM_SURFACELESS_MESA,
+                egl::DEFAULT_DISPLAY as *mut _,
+                attrs.as_ptr(),
+            )
+        })
+        .map(EglDisplay::Ext)
+    }
+
     fn get_display(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
         let mut display = match display {
             RawDisplayHandle::Gbm(handle) => handle.gbm_device,
@@ -443,7 +855,7 @@ impl Display {
 
         supported_features.set(
             DisplayFeatures::CONTEXT_ROBUSTNESS,
-            version > Version::new(1, 5)
+            version >= Version::new(1, 5)
                 || extensions.contains("EGL_EXT_create_context_robustness"),
         );
 
@@ -452,6 +864,11 @@ impl Display {
             extensions.contains("EGL_KHR_create_context_no_error"),
         );
 
+        supported_features.set(
+            DisplayFeatures::CONTEXT_RELEASE_BEHAVIOR,
+            extensions.contains("EGL_KHR_context_flush_control"),
+        );
+
         supported_features
     }
 
@@ -492,6 +909,10 @@ impl Display {
             version,
             display_extensions,
             features,
+            gl_library: OnceCell::new(),
+            gles_library: OnceCell::new(),
+            #[cfg(feature = "thread-safe-display")]
+            lock: Mutex::new(()),
         });
         Ok(Self { inner })
     }
@@ -544,7 +965,14 @@ impl GlDisplay for Display {
     }
 
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void {
-        unsafe { self.inner.egl.GetProcAddress(addr.as_ptr()) as *const _ }
+        let proc_address = unsafe { self.inner.egl.GetProcAddress(addr.as_ptr()) as *const _ };
+        if !proc_address.is_null() {
+            return proc_address;
+        }
+
+        // Some implementations don't return core functions from `eglGetProcAddress`,
+        // thus lookup them in the GL library directly.
+        self.gl_library_symbol(addr)
     }
 
     fn version_string(&self) -> String {
@@ -570,6 +998,29 @@ impl AsRawDisplay for Display {
 
 impl Sealed for Display {}
 
+/// The EGL platform to create the display on with
+/// [`Display::new_with_platform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// The Wayland platform, requires a Wayland display handle.
+    Wayland,
+
+    /// The X11 platform, requires a Xlib or XCB display handle.
+    X11,
+
+    /// The GBM platform, requires a GBM display handle.
+    Gbm,
+
+    /// The surfaceless platform without any native display from
+    /// `EGL_MESA_platform_surfaceless`.
+    Surfaceless,
+}
+
+#[cfg(feature = "thread-safe-display")]
+pub(crate) type DisplayGuard<'a> = MutexGuard<'a, ()>;
+#[cfg(not(feature = "thread-safe-display"))]
+pub(crate) type DisplayGuard<'a> = PhantomData<&'a ()>;
+
 pub(crate) struct DisplayInner {
     /// Pointer to the EGL handler to simplify API calls.
     pub(crate) egl: &'static Egl,
@@ -588,9 +1039,37 @@ pub(crate) struct DisplayInner {
 
     /// The raw display used to create EGL display.
     pub(crate) _native_display: Option<NativeDisplay>,
+
+    /// The GL library to lookup symbols not provided by `eglGetProcAddress`
+    /// for the OpenGL Api.
+    pub(crate) gl_library: OnceCell<Option<Library>>,
+
+    /// The GL library to lookup symbols not provided by `eglGetProcAddress`
+    /// for the OpenGL ES Api.
+    pub(crate) gles_library: OnceCell<Option<Library>>,
+
+    /// The lock serializing the display level calls.
+    #[cfg(feature = "thread-safe-display")]
+    lock: Mutex<()>,
 }
 
 impl DisplayInner {
+    /// Lock the display for the config lookup and the context and surface
+    /// creation, since some drivers crash when they're done concurrently.
+    ///
+    /// This is a no-op unless the `thread-safe-display` feature is enabled,
+    /// since it serializes the creation of the objects across the threads.
+    #[cfg(feature = "thread-safe-display")]
+    pub(crate) fn lock(&self) -> DisplayGuard<'_> {
+        self.lock.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// See the `thread-safe-display` variant.
+    #[cfg(not(feature = "thread-safe-display"))]
+    pub(crate) fn lock(&self) -> DisplayGuard<'_> {
+        PhantomData
+    }
+
     fn uses_display_reference(&self) -> bool {
         if !CLIENT_EXTENSIONS.get().unwrap().contains("EGL_KHR_display_reference") {
             return false;
@@ -727,6 +1206,31 @@ impl Deref for EglDisplay {
     }
 }
 
+/// Check whether the driver or renderer name belongs to the known software
+/// rasterizer.
+pub(crate) fn is_software_renderer(name: &str) -> bool {
+    const SOFTWARE_RENDERERS: [&str; 5] =
+        ["llvmpipe", "softpipe", "swrast", "software rasterizer", "swiftshader"];
+
+    let name = name.to_lowercase();
+    SOFTWARE_RENDERERS.iter().any(|renderer| name.contains(renderer))
+}
+
+/// Load the GL library providing the client API functions.
+fn load_gl_library(api: EGLenum) -> Option<Library> {
+    #[cfg(windows)]
+    let (gl, gles): (&[&str], &[&str]) = (&[], &["libGLESv2.dll"]);
+
+    #[cfg(not(windows))]
+    let (gl, gles): (&[&str], &[&str]) =
+        (&["libGL.so.1", "libGL.so"], &["libGLESv2.so.2", "libGLESv2.so"]);
+
+    // Prefer the library of the Api, but fallback to the other one, since not
+    // every system ships both.
+    let paths = if api == egl::OPENGL_API { [gl, gles] } else { [gles, gl] };
+    paths.into_iter().flatten().find_map(|path| unsafe { Library::new(path).ok() })
+}
+
 /// Collect EGL extensions for the given `display`.
 pub(crate) fn get_extensions(egl: &Egl, display: EGLDisplay) -> HashSet<&'static str> {
     unsafe {
@@ -757,3 +1261,21 @@ pub(crate) unsafe fn extensions_from_ptr(extensions: *const c_char) -> HashSet<&
         HashSet::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn robustness_feature_by_version() {
+        let features = |extensions: &[&'static str], version| {
+            let extensions = extensions.iter().copied().collect();
+            Display::extract_display_features(&extensions, version)
+                .contains(DisplayFeatures::CONTEXT_ROBUSTNESS)
+        };
+
+        assert!(!features(&[], Version::new(1, 4)));
+        assert!(features(&["EGL_EXT_create_context_robustness"], Version::new(1, 4)));
+        assert!(features(&[], Version::new(1, 5)));
+    }
+}
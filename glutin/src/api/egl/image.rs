@@ -0,0 +1,371 @@
+//! Everything related to `EGLImage`.
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, BorrowedFd};
+#[cfg(android_platform)]
+use std::{ffi, mem};
+use std::{fmt, ptr};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLImageKHR, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+
+#[cfg(android_platform)]
+type EglGetNativeClientBufferAndroid =
+    unsafe extern "system" fn(*const ffi::c_void) -> egl::types::EGLClientBuffer;
+
+impl Display {
+    /// Create the image from the level `mipmap_level` of the GL 2D texture
+    /// `texture` owned by the `context`.
+    ///
+    /// The image could be used to share the texture contents with other APIs
+    /// without copying, for example by binding it with
+    /// `glEGLImageTargetTexture2DOES`.
+    ///
+    /// This requires `EGL_KHR_image_base` and `EGL_KHR_gl_texture_2D_image`.
+    pub fn create_image_from_gl_texture_2d<C>(
+        &self,
+        context: &PossiblyCurrentContext<C>,
+        texture: u32,
+        mipmap_level: u32,
+    ) -> Result<Image> {
+        if !self.inner.display_extensions.contains("EGL_KHR_gl_texture_2D_image") {
+            return Err(
+                ErrorKind::NotSupported("EGL_KHR_gl_texture_2D_image is not supported").into()
+            );
+        }
+
+        let attrs =
+            [egl::GL_TEXTURE_LEVEL_KHR as EGLint, mipmap_level as EGLint, egl::NONE as EGLint];
+
+        unsafe {
+            self.create_image(
+                *context.inner.raw,
+                egl::GL_TEXTURE_2D_KHR,
+                texture as usize as egl::types::EGLClientBuffer,
+                &attrs,
+            )
+        }
+    }
+
+    /// Import the dma-buf described by the `planes` as the image of the
+    /// given `width`, `height` and DRM `fourcc` format.
+    ///
+    /// The `modifier` requires `EGL_EXT_image_dma_buf_import_modifiers`,
+    /// which is also required to import more than 3 planes. The file
+    /// descriptors are not consumed and could be closed once the image is
+    /// created.
+    ///
+    /// This requires `EGL_KHR_image_base` and `EGL_EXT_image_dma_buf_import`.
+    #[cfg(unix)]
+    pub fn import_dmabuf(
+        &self,
+        planes: &[DmabufPlane<'_>],
+        width: u32,
+        height: u32,
+        fourcc: u32,
+        modifier: Option<u64>,
+    ) -> Result<Image> {
+        const PLANE_ATTRIBUTES: [[EGLenum; 5]; 4] = [
+            [
+                egl::DMA_BUF_PLANE0_FD_EXT,
+                egl::DMA_BUF_PLANE0_OFFSET_EXT,
+                egl::DMA_BUF_PLANE0_PITCH_EXT,
+                egl::DMA_BUF_PLANE0_MODIFIER_LO_EXT,
+                egl::DMA_BUF_PLANE0_MODIFIER_HI_EXT,
+            ],
+            [
+                egl::DMA_BUF_PLANE1_FD_EXT,
+                egl::DMA_BUF_PLANE1_OFFSET_EXT,
+                egl::DMA_BUF_PLANE1_PITCH_EXT,
+                egl::DMA_BUF_PLANE1_MODIFIER_LO_EXT,
+                egl::DMA_BUF_PLANE1_MODIFIER_HI_EXT,
+            ],
+            [
+                egl::DMA_BUF_PLANE2_FD_EXT,
+                egl::DMA_BUF_PLANE2_OFFSET_EXT,
+                egl::DMA_BUF_PLANE2_PITCH_EXT,
+                egl::DMA_BUF_PLANE2_MODIFIER_LO_EXT,
+                egl::DMA_BUF_PLANE2_MODIFIER_HI_EXT,
+            ],
+            [
+                egl::DMA_BUF_PLANE3_FD_EXT,
+                egl::DMA_BUF_PLANE3_OFFSET_EXT,
+                egl::DMA_BUF_PLANE3_PITCH_EXT,
+                egl::DMA_BUF_PLANE3_MODIFIER_LO_EXT,
+                egl::DMA_BUF_PLANE3_MODIFIER_HI_EXT,
+            ],
+        ];
+
+        if !self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import") {
+            return Err(
+                ErrorKind::NotSupported("EGL_EXT_image_dma_buf_import is not supported").into()
+            );
+        }
+
+        let has_modifiers =
+            self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers");
+        if (modifier.is_some() || planes.len() > 3) && !has_modifiers {
+            return Err(ErrorKind::NotSupported(
+                "EGL_EXT_image_dma_buf_import_modifiers is not supported",
+            )
+            .into());
+        }
+
+        if planes.is_empty() || planes.len() > PLANE_ATTRIBUTES.len() {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(7 + planes.len() * 10);
+        attrs.push(egl::WIDTH as EGLint);
+        attrs.push(width as EGLint);
+        attrs.push(egl::HEIGHT as EGLint);
+        attrs.push(height as EGLint);
+        attrs.push(egl::LINUX_DRM_FOURCC_EXT as EGLint);
+        attrs.push(fourcc as EGLint);
+
+        for (plane, [fd, offset, pitch, modifier_lo, modifier_hi]) in
+            planes.iter().zip(PLANE_ATTRIBUTES)
+        {
+            attrs.push(fd as EGLint);
+            attrs.push(plane.fd.as_raw_fd());
+            attrs.push(offset as EGLint);
+            attrs.push(plane.offset as EGLint);
+            attrs.push(pitch as EGLint);
+            attrs.push(plane.pitch as EGLint);
+
+            if let Some(modifier) = modifier {
+                attrs.push(modifier_lo as EGLint);
+                attrs.push((modifier & 0xFFFF_FFFF) as EGLint);
+                attrs.push(modifier_hi as EGLint);
+                attrs.push((modifier >> 32) as EGLint);
+            }
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        unsafe {
+            self.create_image(egl::NO_CONTEXT, egl::LINUX_DMA_BUF_EXT, ptr::null_mut(), &attrs)
+        }
+    }
+
+    /// Create the image from the `AHardwareBuffer`, which is how the camera
+    /// and video frames are sampled in GL on Android.
+    ///
+    /// This requires `EGL_KHR_image_base`, `EGL_ANDROID_image_native_buffer`
+    /// and `EGL_ANDROID_get_native_client_buffer`.
+    ///
+    /// # Safety
+    ///
+    /// The `hardware_buffer` must point to a valid `AHardwareBuffer`.
+    #[cfg(android_platform)]
+    pub unsafe fn create_image_from_hardware_buffer(
+        &self,
+        hardware_buffer: *const ffi::c_void,
+    ) -> Result<Image> {
+        if !self.inner.display_extensions.contains("EGL_ANDROID_image_native_buffer") {
+            return Err(ErrorKind::NotSupported(
+                "EGL_ANDROID_image_native_buffer is not supported",
+            )
+            .into());
+        }
+
+        if !self.inner.display_extensions.contains("EGL_ANDROID_get_native_client_buffer") {
+            return Err(ErrorKind::NotSupported(
+                "EGL_ANDROID_get_native_client_buffer is not supported",
+            )
+            .into());
+        }
+
+        let get_native_client_buffer = unsafe {
+            self.inner.egl.GetProcAddress(b"eglGetNativeClientBufferANDROID\0".as_ptr() as *const _)
+        };
+        if get_native_client_buffer.is_null() {
+            return Err(ErrorKind::NotSupported(
+                "eglGetNativeClientBufferANDROID is not supported",
+            )
+            .into());
+        }
+
+        let buffer = unsafe {
+            let get_native_client_buffer: EglGetNativeClientBufferAndroid =
+                mem::transmute(get_native_client_buffer);
+            get_native_client_buffer(hardware_buffer)
+        };
+        if buffer.is_null() {
+            return Err(super::check_error()
+                .err()
+                .unwrap_or_else(|| ErrorKind::BadParameter.into()));
+        }
+
+        let attrs = [egl::IMAGE_PRESERVED_KHR as EGLint, egl::TRUE as EGLint, egl::NONE as EGLint];
+        unsafe { self.create_image(egl::NO_CONTEXT, egl::NATIVE_BUFFER_ANDROID, buffer, &attrs) }
+    }
+
+    /// Query the DRM fourcc formats supported by [`Self::import_dmabuf`].
+    ///
+    /// This requires `EGL_EXT_image_dma_buf_import_modifiers`.
+    pub fn query_dmabuf_formats(&self) -> Result<Vec<u32>> {
+        self.ensure_dmabuf_modifiers()?;
+
+        unsafe {
+            let mut num_formats = 0;
+            if self.inner.egl.QueryDmaBufFormatsEXT(
+                *self.inner.raw,
+                0,
+                ptr::null_mut(),
+                &mut num_formats,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            let mut formats = vec![0; num_formats as usize];
+            if self.inner.egl.QueryDmaBufFormatsEXT(
+                *self.inner.raw,
+                num_formats,
+                formats.as_mut_ptr(),
+                &mut num_formats,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            formats.truncate(num_formats as usize);
+            Ok(formats.into_iter().map(|format| format as u32).collect())
+        }
+    }
+
+    /// Query the modifiers supported by [`Self::import_dmabuf`] for the DRM
+    /// fourcc `format`.
+    ///
+    /// This requires `EGL_EXT_image_dma_buf_import_modifiers`.
+    pub fn query_dmabuf_modifiers(&self, format: u32) -> Result<Vec<u64>> {
+        self.ensure_dmabuf_modifiers()?;
+
+        unsafe {
+            let mut num_modifiers = 0;
+            if self.inner.egl.QueryDmaBufModifiersEXT(
+                *self.inner.raw,
+                format as EGLint,
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            let mut modifiers = vec![0; num_modifiers as usize];
+            if self.inner.egl.QueryDmaBufModifiersEXT(
+                *self.inner.raw,
+                format as EGLint,
+                num_modifiers,
+                modifiers.as_mut_ptr(),
+                ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            modifiers.truncate(num_modifiers as usize);
+            Ok(modifiers)
+        }
+    }
+
+    fn ensure_dmabuf_modifiers(&self) -> Result<()> {
+        if self.inner.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL_EXT_image_dma_buf_import_modifiers is not supported")
+                .into())
+        }
+    }
+
+    /// Create the image with `eglCreateImageKHR`.
+    ///
+    /// # Safety
+    ///
+    /// The `buffer` must be valid for the `target` and the `attrs` must be
+    /// terminated with `EGL_NONE`.
+    pub(crate) unsafe fn create_image(
+        &self,
+        context: egl::types::EGLContext,
+        target: EGLenum,
+        buffer: egl::types::EGLClientBuffer,
+        attrs: &[EGLint],
+    ) -> Result<Image> {
+        if !self.inner.display_extensions.contains("EGL_KHR_image_base") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_image_base is not supported").into());
+        }
+
+        let image = unsafe {
+            self.inner.egl.CreateImageKHR(*self.inner.raw, context, target, buffer, attrs.as_ptr())
+        };
+
+        if image == egl::NO_IMAGE_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Image { display: self.clone(), raw: image })
+    }
+}
+
+/// The plane of the dma-buf passed to [`Display::import_dmabuf`].
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+pub struct DmabufPlane<'a> {
+    /// The dma-buf file descriptor of the plane.
+    pub fd: BorrowedFd<'a>,
+
+    /// The offset of the plane in bytes.
+    pub offset: u32,
+
+    /// The pitch of the plane in bytes.
+    pub pitch: u32,
+}
+
+/// A wrapper around `EGLImageKHR`.
+///
+/// The image is destroyed on drop, however the resources it was created from
+/// are kept alive by the underlying Api while they are in use.
+pub struct Image {
+    display: Display,
+    raw: EGLImageKHR,
+}
+
+// The `EGLImage` could be accessed from any thread.
+unsafe impl Send for Image {}
+unsafe impl Sync for Image {}
+
+impl Image {
+    /// Get a raw handle to the `EGLImageKHR`, which could be passed to
+    /// `glEGLImageTargetTexture2DOES`.
+    pub fn raw_image(&self) -> EGLImageKHR {
+        self.raw
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyImageKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -6,8 +6,9 @@
 //! The EGL platform allows creating a [`Display`](self::display::Display) from
 //! a [`Device`](self::device::Device).
 
-use std::ffi::{self, CString};
+use std::ffi::{self, CStr, CString};
 use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
 
 use glutin_egl_sys::egl;
 
@@ -24,24 +25,105 @@ use crate::lib_loading::{SymLoading, SymWrapper};
 
 pub mod config;
 pub mod context;
+pub mod debug;
 pub mod device;
 pub mod display;
+pub mod image;
+pub mod stream;
 pub mod surface;
+pub mod sync;
 
 pub(crate) static EGL: Lazy<Option<Egl>> = Lazy::new(|| {
+    let mut state = EGL_LOADER.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(loader) = state.loader.as_ref() {
+        let egl = egl::Egl::load_with(|sym_name| {
+            let sym_name = CString::new(sym_name.as_bytes()).unwrap();
+            loader(&sym_name)
+        });
+        return Some(Egl::Loader(egl));
+    }
+
+    state.system_library = true;
+
     #[cfg(windows)]
     let paths = ["libEGL.dll", "atioglxx.dll"];
 
     #[cfg(not(windows))]
     let paths = ["libEGL.so.1", "libEGL.so"];
 
-    unsafe { SymWrapper::new(&paths).map(Egl).ok() }
+    unsafe { SymWrapper::new(&paths).map(Egl::Library).ok() }
 });
 
+type EglLoader = dyn Fn(&CStr) -> *const ffi::c_void + Send + Sync;
+
+/// The user provided loader used instead of the system `libEGL`, locked while
+/// loading EGL.
+static EGL_LOADER: Mutex<EglLoaderState> =
+    Mutex::new(EglLoaderState { loader: None, system_library: false });
+
+struct EglLoaderState {
+    /// The first registered loader.
+    loader: Option<Box<EglLoader>>,
+
+    /// Whether EGL was loaded from the system library, so the loader can't
+    /// take effect anymore.
+    system_library: bool,
+}
+
+/// Load the EGL functions with the `loader`, failing when they were already
+/// loaded differently.
+///
+/// The first registered loader wins, registering the same loader again is
+/// accepted.
+pub(crate) fn load_with_loader(loader: Box<EglLoader>) -> Result<&'static Egl> {
+    {
+        let mut state = EGL_LOADER.lock().unwrap_or_else(|err| err.into_inner());
+        match state.loader.as_ref() {
+            Some(current) if same_loader(current.as_ref(), loader.as_ref()) => (),
+            Some(_) => {
+                return Err(ErrorKind::NotSupported("different EGL loader was already set").into())
+            },
+            None if state.system_library => {
+                return Err(ErrorKind::NotSupported(
+                    "EGL was already loaded from the system library",
+                )
+                .into())
+            },
+            None => state.loader = Some(loader),
+        }
+    }
+
+    EGL.as_ref().ok_or_else(|| ErrorKind::NotFound.into())
+}
+
+/// The display of the first EGL device for the tests, `None` when EGL or the
+/// devices are not available.
+#[cfg(test)]
+pub(crate) fn test_display() -> Option<display::Display> {
+    device::Device::query_devices()
+        .ok()?
+        .next()
+        .and_then(|device| unsafe { display::Display::with_device(&device, None) }.ok())
+}
+
+/// Whether the loaders resolve the same `eglGetProcAddress`, since the
+/// closures can't be compared directly.
+fn same_loader(lhs: &EglLoader, rhs: &EglLoader) -> bool {
+    let name = CStr::from_bytes_with_nul(b"eglGetProcAddress\0").unwrap();
+    let address = lhs(name);
+    !address.is_null() && address == rhs(name)
+}
+
 type EglGetProcAddress = unsafe extern "C" fn(*const ffi::c_void) -> *const ffi::c_void;
 static EGL_GET_PROC_ADDRESS: OnceCell<libloading_os::Symbol<EglGetProcAddress>> = OnceCell::new();
 
-pub(crate) struct Egl(pub SymWrapper<egl::Egl>);
+pub(crate) enum Egl {
+    /// The functions loaded from the system library.
+    Library(SymWrapper<egl::Egl>),
+
+    /// The functions loaded with the user provided loader.
+    Loader(egl::Egl),
+}
 
 unsafe impl Sync for Egl {}
 unsafe impl Send for Egl {}
@@ -76,40 +158,49 @@ impl Deref for Egl {
     type Target = egl::Egl;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        match self {
+            Self::Library(egl) => egl,
+            Self::Loader(egl) => egl,
+        }
     }
 }
 
 impl DerefMut for Egl {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        match self {
+            Self::Library(egl) => egl,
+            Self::Loader(egl) => egl,
+        }
     }
 }
 
 /// Obtain the error from the EGL.
 fn check_error() -> Result<()> {
     let egl = EGL.as_ref().unwrap();
-    unsafe {
-        let raw_code = egl.GetError() as egl::types::EGLenum;
-        let kind = match raw_code {
-            egl::SUCCESS => return Ok(()),
-            egl::NOT_INITIALIZED => ErrorKind::InitializationFailed,
-            egl::BAD_ACCESS => ErrorKind::BadAccess,
-            egl::BAD_ALLOC => ErrorKind::OutOfMemory,
-            egl::BAD_ATTRIBUTE => ErrorKind::BadAttribute,
-            egl::BAD_CONTEXT => ErrorKind::BadContext,
-            egl::BAD_CONFIG => ErrorKind::BadConfig,
-            egl::BAD_CURRENT_SURFACE => ErrorKind::BadCurrentSurface,
-            egl::BAD_DISPLAY => ErrorKind::BadDisplay,
-            egl::BAD_SURFACE => ErrorKind::BadSurface,
-            egl::BAD_MATCH => ErrorKind::BadMatch,
-            egl::BAD_PARAMETER => ErrorKind::BadParameter,
-            egl::BAD_NATIVE_PIXMAP => ErrorKind::BadNativePixmap,
-            egl::BAD_NATIVE_WINDOW => ErrorKind::BadNativeWindow,
-            egl::CONTEXT_LOST => ErrorKind::ContextLost,
-            _ => ErrorKind::Misc,
-        };
+    unsafe { error_from_code(egl.GetError() as egl::types::EGLenum) }
+}
 
-        Err(Error::new(Some(raw_code as i64), None, kind))
-    }
+/// Convert the raw EGL error code into the error.
+fn error_from_code(raw_code: egl::types::EGLenum) -> Result<()> {
+    let (kind, name) = match raw_code {
+        egl::SUCCESS => return Ok(()),
+        egl::NOT_INITIALIZED => (ErrorKind::InitializationFailed, "EGL_NOT_INITIALIZED"),
+        egl::BAD_ACCESS => (ErrorKind::BadAccess, "EGL_BAD_ACCESS"),
+        egl::BAD_ALLOC => (ErrorKind::OutOfMemory, "EGL_BAD_ALLOC"),
+        egl::BAD_ATTRIBUTE => (ErrorKind::BadAttribute, "EGL_BAD_ATTRIBUTE"),
+        egl::BAD_CONTEXT => (ErrorKind::BadContext, "EGL_BAD_CONTEXT"),
+        egl::BAD_CONFIG => (ErrorKind::BadConfig, "EGL_BAD_CONFIG"),
+        egl::BAD_CURRENT_SURFACE => (ErrorKind::BadCurrentSurface, "EGL_BAD_CURRENT_SURFACE"),
+        egl::BAD_DISPLAY => (ErrorKind::BadDisplay, "EGL_BAD_DISPLAY"),
+        egl::BAD_SURFACE => (ErrorKind::BadSurface, "EGL_BAD_SURFACE"),
+        egl::BAD_MATCH => (ErrorKind::BadMatch, "EGL_BAD_MATCH"),
+        egl::BAD_PARAMETER => (ErrorKind::BadParameter, "EGL_BAD_PARAMETER"),
+        egl::BAD_NATIVE_PIXMAP => (ErrorKind::BadNativePixmap, "EGL_BAD_NATIVE_PIXMAP"),
+        egl::BAD_NATIVE_WINDOW => (ErrorKind::BadNativeWindow, "EGL_BAD_NATIVE_WINDOW"),
+        egl::CONTEXT_LOST => (ErrorKind::ContextLost, "EGL_CONTEXT_LOST"),
+        _ => (ErrorKind::Misc, "unknown EGL error"),
+    };
+
+    let message = format!("{name}: {}", kind.as_str());
+    Err(Error::new(Some(raw_code as i64), Some(message), kind))
 }
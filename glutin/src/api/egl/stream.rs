@@ -0,0 +1,203 @@
+//! Everything related to `EGLStreamKHR`.
+
+use std::ffi::c_void;
+use std::fmt;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLStreamKHR, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+
+impl Display {
+    /// Create the stream transferring the frames from the producer to the
+    /// consumer.
+    ///
+    /// When `fifo_length` is `0` the stream operates in mailbox mode, where
+    /// the consumer always gets the latest frame, otherwise the frames are
+    /// queued up to the given length.
+    ///
+    /// This requires `EGL_KHR_stream`, `EGL_KHR_stream_fifo` is also required
+    /// for the non zero `fifo_length`.
+    pub fn create_stream(&self, fifo_length: u32) -> Result<Stream> {
+        if !self.inner.display_extensions.contains("EGL_KHR_stream") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_stream is not supported").into());
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(3);
+        if fifo_length != 0 {
+            if !self.inner.display_extensions.contains("EGL_KHR_stream_fifo") {
+                return Err(ErrorKind::NotSupported("EGL_KHR_stream_fifo is not supported").into());
+            }
+
+            attrs.push(egl::STREAM_FIFO_LENGTH_KHR as EGLint);
+            attrs.push(fifo_length as EGLint);
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        let stream = unsafe { self.inner.egl.CreateStreamKHR(*self.inner.raw, attrs.as_ptr()) };
+        if stream == egl::NO_STREAM_KHR {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Stream { display: self.clone(), raw: stream })
+    }
+}
+
+/// A wrapper around `EGLStreamKHR`.
+///
+/// The producer, like the EGL output layer or the video decoder, is connected
+/// to the stream with the raw handle obtained from [`Stream::raw_stream`].
+pub struct Stream {
+    display: Display,
+    raw: EGLStreamKHR,
+}
+
+// The `EGLStreamKHR` could be accessed from any thread.
+unsafe impl Send for Stream {}
+unsafe impl Sync for Stream {}
+
+impl Stream {
+    /// Query the current state of the stream.
+    pub fn state(&self) -> Result<StreamState> {
+        let mut state = 0;
+        unsafe {
+            if self.display.inner.egl.QueryStreamKHR(
+                *self.display.inner.raw,
+                self.raw,
+                egl::STREAM_STATE_KHR,
+                &mut state,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        let state = match state as EGLenum {
+            egl::STREAM_STATE_CREATED_KHR => StreamState::Created,
+            egl::STREAM_STATE_CONNECTING_KHR => StreamState::Connecting,
+            egl::STREAM_STATE_EMPTY_KHR => StreamState::Empty,
+            egl::STREAM_STATE_NEW_FRAME_AVAILABLE_KHR => StreamState::NewFrameAvailable,
+            egl::STREAM_STATE_OLD_FRAME_AVAILABLE_KHR => StreamState::OldFrameAvailable,
+            _ => StreamState::Disconnected,
+        };
+
+        Ok(state)
+    }
+
+    /// Connect the external texture currently bound to
+    /// `GL_TEXTURE_EXTERNAL_OES` on the `context` as the consumer of the
+    /// stream.
+    ///
+    /// This requires `EGL_KHR_stream_consumer_gltexture`.
+    pub fn consumer_gl_texture_external<C>(
+        &self,
+        context: &PossiblyCurrentContext<C>,
+    ) -> Result<()> {
+        self.ensure_consumer_gltexture()?;
+        context.inner.bind_api();
+        unsafe {
+            if self
+                .display
+                .inner
+                .egl
+                .StreamConsumerGLTextureExternalKHR(*self.display.inner.raw, self.raw)
+                == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Latch the most recent frame from the producer into the texture
+    /// connected with [`Self::consumer_gl_texture_external`].
+    pub fn consumer_acquire<C>(&self, context: &PossiblyCurrentContext<C>) -> Result<()> {
+        self.ensure_consumer_gltexture()?;
+        context.inner.bind_api();
+        unsafe {
+            if self.display.inner.egl.StreamConsumerAcquireKHR(*self.display.inner.raw, self.raw)
+                == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Release the frame previously latched with [`Self::consumer_acquire`]
+    /// back to the producer.
+    pub fn consumer_release<C>(&self, context: &PossiblyCurrentContext<C>) -> Result<()> {
+        self.ensure_consumer_gltexture()?;
+        context.inner.bind_api();
+        unsafe {
+            if self.display.inner.egl.StreamConsumerReleaseKHR(*self.display.inner.raw, self.raw)
+                == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Get a raw handle to the `EGLStreamKHR`.
+    pub fn raw_stream(&self) -> *const c_void {
+        self.raw
+    }
+
+    fn ensure_consumer_gltexture(&self) -> Result<()> {
+        if self.display.inner.display_extensions.contains("EGL_KHR_stream_consumer_gltexture") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL_KHR_stream_consumer_gltexture is not supported")
+                .into())
+        }
+    }
+}
+
+/// The state of the [`Stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamState {
+    /// The stream was created, but neither producer nor consumer are
+    /// connected.
+    Created,
+
+    /// The consumer is connected, but the producer is not.
+    Connecting,
+
+    /// Both producer and consumer are connected, but no frame was produced.
+    Empty,
+
+    /// The producer inserted the frame which the consumer didn't acquire.
+    NewFrameAvailable,
+
+    /// The consumer already acquired the latest frame.
+    OldFrameAvailable,
+
+    /// The producer or the consumer was destroyed, so the stream is unusable.
+    Disconnected,
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyStreamKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -1,11 +1,15 @@
 //! Everything related to `EGLSurface`.
 
+use std::cell::Cell;
+use std::ffi::CString;
 use std::marker::PhantomData;
+use std::mem;
 use std::num::NonZeroU32;
-use std::{ffi, fmt};
+use std::{ffi, fmt, ptr};
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLAttrib, EGLSurface, EGLint};
+use glutin_egl_sys::egl::types::EGLBoolean;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLSurface, EGLenum, EGLint};
 use raw_window_handle::RawWindowHandle;
 #[cfg(wayland_platform)]
 use wayland_sys::{egl::*, ffi_dispatch};
@@ -13,12 +17,12 @@ use wayland_sys::{egl::*, ffi_dispatch};
 use crate::api::egl::display::EglDisplay;
 use crate::config::GetGlConfig;
 use crate::display::GetGlDisplay;
-use crate::error::{ErrorKind, Result};
+use crate::error::{Error, ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface, Rect, SurfaceAttributes,
-    SurfaceTypeTrait, SwapInterval, WindowSurface,
+    AsRawSurface, ColorSpace, NativePixmap, PbufferSurface, PixmapSurface, RawSurface, Rect,
+    SurfaceAttributes, SurfaceType, SurfaceTypeTrait, SwapInterval, TextureFormat, WindowSurface,
 };
 
 use super::config::Config;
@@ -28,6 +32,41 @@ use super::display::Display;
 /// Hint for the attribute list size.
 const ATTR_SIZE_HINT: usize = 8;
 
+type EglLockSurfaceKhr =
+    unsafe extern "system" fn(egl::types::EGLDisplay, EGLSurface, *const EGLint) -> EGLBoolean;
+type EglUnlockSurfaceKhr =
+    unsafe extern "system" fn(egl::types::EGLDisplay, EGLSurface) -> EGLBoolean;
+type EglQuerySurface64Khr = unsafe extern "system" fn(
+    egl::types::EGLDisplay,
+    EGLSurface,
+    EGLint,
+    *mut EGLAttrib,
+) -> EGLBoolean;
+
+#[cfg(android_platform)]
+type EglGetNextFrameIdAndroid =
+    unsafe extern "system" fn(egl::types::EGLDisplay, EGLSurface, *mut u64) -> EGLBoolean;
+#[cfg(android_platform)]
+type EglGetFrameTimestampSupportedAndroid =
+    unsafe extern "system" fn(egl::types::EGLDisplay, EGLSurface, EGLint) -> EGLBoolean;
+#[cfg(android_platform)]
+type EglGetFrameTimestampsAndroid = unsafe extern "system" fn(
+    egl::types::EGLDisplay,
+    EGLSurface,
+    u64,
+    EGLint,
+    *const EGLint,
+    *mut i64,
+) -> EGLBoolean;
+#[cfg(android_platform)]
+type EglGetCompositorTimingAndroid = unsafe extern "system" fn(
+    egl::types::EGLDisplay,
+    EGLSurface,
+    EGLint,
+    *const EGLint,
+    *mut i64,
+) -> EGLBoolean;
+
 impl Display {
     pub(crate) unsafe fn create_pbuffer_surface(
         &self,
@@ -37,6 +76,13 @@ impl Display {
         let width = surface_attributes.width.unwrap();
         let height = surface_attributes.height.unwrap();
 
+        // Validate the size against the config limits, since the driver reports
+        // the vague `EGL_BAD_ALLOC` or `EGL_BAD_MATCH` otherwise. The largest
+        // pbuffer is clamped by the driver instead.
+        if !surface_attributes.largest_pbuffer {
+            Self::check_pbuffer_size(config, width.get(), height.get())?;
+        }
+
         // XXX Window surface is using `EGLAttrib` and not `EGLint`.
         let mut attrs = Vec::<EGLint>::with_capacity(ATTR_SIZE_HINT);
 
@@ -47,12 +93,50 @@ impl Display {
         attrs.push(egl::HEIGHT as EGLint);
         attrs.push(height.get() as EGLint);
 
+        // Let the driver clamp the size to the largest available pbuffer.
+        if surface_attributes.largest_pbuffer {
+            attrs.push(egl::LARGEST_PBUFFER as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
+        // Make the pbuffer bindable to the texture.
+        if let Some(texture_format) = surface_attributes.texture_format {
+            let (bind_to_texture, format) = match texture_format {
+                TextureFormat::Rgb => (egl::BIND_TO_TEXTURE_RGB, egl::TEXTURE_RGB),
+                TextureFormat::Rgba => (egl::BIND_TO_TEXTURE_RGBA, egl::TEXTURE_RGBA),
+            };
+
+            if unsafe { config.raw_attribute(bind_to_texture as EGLint) } != egl::TRUE as EGLint {
+                return Err(ErrorKind::NotSupported(
+                    "the config can't be bound to the texture with the given format",
+                )
+                .into());
+            }
+
+            attrs.push(egl::TEXTURE_FORMAT as EGLint);
+            attrs.push(format as EGLint);
+            attrs.push(egl::TEXTURE_TARGET as EGLint);
+            attrs.push(egl::TEXTURE_2D as EGLint);
+
+            if surface_attributes.mipmap_texture {
+                attrs.push(egl::MIPMAP_TEXTURE as EGLint);
+                attrs.push(egl::TRUE as EGLint);
+            }
+        }
+
+        if surface_attributes.protected_content {
+            self.ensure_protected_surface()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLint);
 
         let config = config.clone();
+        let _guard = self.inner.lock();
         let surface = unsafe {
-            Self::check_surface_error(self.inner.egl.CreatePbufferSurface(
+            Self::check_surface_error::<PbufferSurface>(self.inner.egl.CreatePbufferSurface(
                 *self.inner.raw,
                 *config.inner.raw,
                 attrs.as_ptr(),
@@ -64,6 +148,8 @@ impl Display {
             native_window: None,
             config,
             raw: surface,
+            attributes: surface_attributes.clone(),
+            label: Cell::new(None),
             _ty: PhantomData,
         })
     }
@@ -77,6 +163,10 @@ impl Display {
 
         let mut attrs = Vec::<EGLAttrib>::with_capacity(ATTR_SIZE_HINT);
 
+        if surface_attributes.srgb == Some(true) && !config.srgb_capable() {
+            return Err(ErrorKind::NotSupported("sRGB framebuffers are not supported").into());
+        }
+
         if surface_attributes.srgb.is_some() && config.srgb_capable() {
             attrs.push(egl::GL_COLORSPACE as EGLAttrib);
             let colorspace = match surface_attributes.srgb {
@@ -86,10 +176,17 @@ impl Display {
             attrs.push(colorspace);
         }
 
+        if surface_attributes.protected_content {
+            self.ensure_protected_surface()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLAttrib);
+            attrs.push(egl::TRUE as EGLAttrib);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLAttrib);
 
         let config = config.clone();
+        let _guard = self.inner.lock();
         let surface = match self.inner.raw {
             EglDisplay::Khr(display) => {
                 let platform_pixmap = native_pixmap.as_platform_pixmap();
@@ -146,13 +243,15 @@ impl Display {
             },
         };
 
-        let surface = Self::check_surface_error(surface)?;
+        let surface = Self::check_surface_error::<PixmapSurface>(surface)?;
 
         Ok(Surface {
             display: self.clone(),
             config,
             native_window: None,
             raw: surface,
+            attributes: surface_attributes.clone(),
+            label: Cell::new(None),
             _ty: PhantomData,
         })
     }
@@ -169,31 +268,11 @@ impl Display {
             surface_attributes.raw_window_handle.as_ref().unwrap(),
         )?;
 
-        // XXX Window surface is using `EGLAttrib` and not `EGLint`.
-        let mut attrs = Vec::<EGLAttrib>::with_capacity(ATTR_SIZE_HINT);
-
-        // Add information about render buffer.
-        attrs.push(egl::RENDER_BUFFER as EGLAttrib);
-        let buffer =
-            if surface_attributes.single_buffer { egl::SINGLE_BUFFER } else { egl::BACK_BUFFER }
-                as EGLAttrib;
-        attrs.push(buffer);
-
-        // // Add colorspace if the extension is present.
-        if surface_attributes.srgb.is_some() && config.srgb_capable() {
-            attrs.push(egl::GL_COLORSPACE as EGLAttrib);
-            let colorspace = match surface_attributes.srgb {
-                Some(true) => egl::GL_COLORSPACE_SRGB as EGLAttrib,
-                _ => egl::GL_COLORSPACE_LINEAR as EGLAttrib,
-            };
-            attrs.push(colorspace);
-        }
-
-        // Push `egl::NONE` to terminate the list.
-        attrs.push(egl::NONE as EGLAttrib);
+        let attrs = self.window_surface_attributes(config, surface_attributes)?;
 
         let config = config.clone();
 
+        let _guard = self.inner.lock();
         let surface = match self.inner.raw {
             EglDisplay::Khr(display) => unsafe {
                 self.inner.egl.CreatePlatformWindowSurface(
@@ -223,20 +302,155 @@ impl Display {
             },
         };
 
-        let surface = Self::check_surface_error(surface)?;
+        let surface = Self::check_surface_error::<WindowSurface>(surface)?;
 
         Ok(Surface {
             display: self.clone(),
             config,
             native_window: Some(native_window),
             raw: surface,
+            attributes: surface_attributes.clone(),
+            label: Cell::new(None),
             _ty: PhantomData,
         })
     }
 
-    fn check_surface_error(surface: EGLSurface) -> Result<EGLSurface> {
+    /// Check the pbuffer size against `EGL_MAX_PBUFFER_*` limits of the
+    /// config.
+    fn check_pbuffer_size(config: &Config, width: u32, height: u32) -> Result<()> {
+        let limit = |attr: EGLenum| unsafe { config.raw_attribute(attr as EGLint) as u64 };
+
+        if width as u64 > limit(egl::MAX_PBUFFER_WIDTH) {
+            return Err(ErrorKind::NotSupported(
+                "pbuffer width exceeds EGL_MAX_PBUFFER_WIDTH of the config",
+            )
+            .into());
+        }
+
+        if height as u64 > limit(egl::MAX_PBUFFER_HEIGHT) {
+            return Err(ErrorKind::NotSupported(
+                "pbuffer height exceeds EGL_MAX_PBUFFER_HEIGHT of the config",
+            )
+            .into());
+        }
+
+        if width as u64 * height as u64 > limit(egl::MAX_PBUFFER_PIXELS) {
+            return Err(ErrorKind::NotSupported(
+                "pbuffer size exceeds EGL_MAX_PBUFFER_PIXELS of the config",
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Build the attributes list for `eglCreateWindowSurface`, validating
+    /// them against the config and the display, so the unsupported
+    /// combinations are reported precisely instead of the generic
+    /// `EGL_BAD_ATTRIBUTE`.
+    fn window_surface_attributes(
+        &self,
+        config: &Config,
+        surface_attributes: &SurfaceAttributes<WindowSurface>,
+    ) -> Result<Vec<EGLAttrib>> {
+        let surface_type = unsafe { config.raw_attribute(egl::SURFACE_TYPE as EGLint) };
+        if surface_type & egl::WINDOW_BIT as EGLint == 0 {
+            return Err(
+                ErrorKind::NotSupported("the config doesn't support window surfaces").into()
+            );
+        }
+
+        // XXX Window surface is using `EGLAttrib` and not `EGLint`.
+        let mut attrs = Vec::<EGLAttrib>::with_capacity(ATTR_SIZE_HINT);
+
+        // Add information about render buffer.
+        attrs.push(egl::RENDER_BUFFER as EGLAttrib);
+        let buffer =
+            if surface_attributes.single_buffer { egl::SINGLE_BUFFER } else { egl::BACK_BUFFER }
+                as EGLAttrib;
+        attrs.push(buffer);
+
+        // Add colorspace if the extension is present.
+        if let Some(colorspace) = surface_attributes.colorspace {
+            let (extension, colorspace) = match colorspace {
+                ColorSpace::Linear => ("EGL_KHR_gl_colorspace", egl::GL_COLORSPACE_LINEAR),
+                ColorSpace::Srgb => ("EGL_KHR_gl_colorspace", egl::GL_COLORSPACE_SRGB),
+                ColorSpace::ScRgb => ("EGL_EXT_gl_colorspace_scrgb", egl::GL_COLORSPACE_SCRGB_EXT),
+                ColorSpace::ScRgbLinear => {
+                    ("EGL_EXT_gl_colorspace_scrgb_linear", egl::GL_COLORSPACE_SCRGB_LINEAR_EXT)
+                },
+                ColorSpace::Bt2020Linear => {
+                    ("EGL_EXT_gl_colorspace_bt2020_linear", egl::GL_COLORSPACE_BT2020_LINEAR_EXT)
+                },
+                ColorSpace::Bt2020Pq => {
+                    ("EGL_EXT_gl_colorspace_bt2020_pq", egl::GL_COLORSPACE_BT2020_PQ_EXT)
+                },
+            };
+
+            if !self.inner.display_extensions.contains(extension) {
+                return Err(ErrorKind::NotSupported(
+                    "the requested colorspace is not supported by the display",
+                )
+                .into());
+            }
+
+            attrs.push(egl::GL_COLORSPACE as EGLAttrib);
+            attrs.push(colorspace as EGLAttrib);
+        } else {
+            match surface_attributes.srgb {
+                Some(true) if !config.srgb_capable() => {
+                    return Err(
+                        ErrorKind::NotSupported("EGL_KHR_gl_colorspace is not supported").into()
+                    );
+                },
+                Some(srgb) if config.srgb_capable() => {
+                    attrs.push(egl::GL_COLORSPACE as EGLAttrib);
+                    let colorspace =
+                        if srgb { egl::GL_COLORSPACE_SRGB } else { egl::GL_COLORSPACE_LINEAR };
+                    attrs.push(colorspace as EGLAttrib);
+                },
+                // The linear colorspace is the default one.
+                _ => (),
+            }
+        }
+
+        if surface_attributes.protected_content {
+            self.ensure_protected_surface()?;
+            attrs.push(egl::PROTECTED_CONTENT_EXT as EGLAttrib);
+            attrs.push(egl::TRUE as EGLAttrib);
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLAttrib);
+
+        Ok(attrs)
+    }
+
+    /// Ensure that the protected surfaces are supported, so the protected
+    /// request doesn't silently produce the unprotected surface.
+    fn ensure_protected_surface(&self) -> Result<()> {
+        let extensions = &self.inner.display_extensions;
+        if extensions.contains("EGL_EXT_protected_surface")
+            || extensions.contains("EGL_EXT_protected_content")
+        {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL_EXT_protected_surface is not supported").into())
+        }
+    }
+
+    fn check_surface_error<T: SurfaceTypeTrait>(surface: EGLSurface) -> Result<EGLSurface> {
         if surface == egl::NO_SURFACE {
-            Err(super::check_error().err().unwrap())
+            // Some drivers don't set the error, like when the native window was
+            // destroyed under them, so report the likely cause instead.
+            Err(super::check_error().err().unwrap_or_else(|| {
+                match T::surface_type() {
+                    SurfaceType::Window => ErrorKind::BadNativeWindow,
+                    SurfaceType::Pixmap => ErrorKind::BadNativePixmap,
+                    SurfaceType::Pbuffer => ErrorKind::OutOfMemory,
+                }
+                .into()
+            }))
         } else {
             Ok(surface)
         }
@@ -249,6 +463,8 @@ pub struct Surface<T: SurfaceTypeTrait> {
     config: Config,
     pub(crate) raw: EGLSurface,
     native_window: Option<NativeWindow>,
+    attributes: SurfaceAttributes<T>,
+    label: Cell<Option<CString>>,
     _ty: PhantomData<T>,
 }
 
@@ -265,13 +481,25 @@ impl<T: SurfaceTypeTrait> Surface<T> {
     ///
     /// This Api doesn't do any partial rendering, it just provides hints for
     /// the system compositor.
-    pub fn swap_buffers_with_damage(
+    ///
+    /// The `rects` are in surface pixel coordinates with the origin in the
+    /// **bottom left** corner of the surface, like the rest of OpenGL, and
+    /// not in the top left corner like most windowing systems. A rect damaging
+    /// the top row of a surface with height `h` is `Rect::new(0, h - 1, w,
+    /// 1)`. The `rects` are clamped to the surface size, since some drivers
+    /// misbehave otherwise, and the ones outside of the surface are dropped.
+    /// When `rects` isn't empty, but none of them is inside of the surface,
+    /// [`ErrorKind::BadParameter`] is returned without swapping the buffers,
+    /// since passing no rects would damage the entire surface.
+    pub fn swap_buffers_with_damage<C>(
         &self,
-        context: &PossiblyCurrentContext,
+        context: &PossiblyCurrentContext<C>,
         rects: &[Rect],
     ) -> Result<()> {
         context.inner.bind_api();
 
+        let rects = self.clamp_rects(rects)?;
+
         let res = unsafe {
             if self.display.inner.display_extensions.contains("EGL_KHR_swap_buffers_with_damage") {
                 self.display.inner.egl.SwapBuffersWithDamageKHR(
@@ -298,12 +526,305 @@ impl<T: SurfaceTypeTrait> Surface<T> {
         };
 
         if res == egl::FALSE {
-            super::check_error()
+            check_swap_error()
         } else {
             Ok(())
         }
     }
 
+    /// Declare the region of the back buffer which is going to be updated
+    /// in the current frame, so the contents outside of it could be kept.
+    ///
+    /// This must be called after making the `context` current with the
+    /// surface and querying [`GlSurface::buffer_age`], which is used to
+    /// compute the region, but before the first draw of the frame. The
+    /// rendering outside of the region is undefined. The `rects` use the same
+    /// coordinates as in [`Self::swap_buffers_with_damage`] and are clamped to
+    /// the surface size the same way, dropping the ones outside of it. When
+    /// `rects` isn't empty, but none of them is inside of the surface,
+    /// [`ErrorKind::BadParameter`] is returned without setting the region,
+    /// since passing no rects would declare the entire surface.
+    ///
+    /// This requires `EGL_KHR_partial_update`.
+    pub fn set_damage_region<C>(
+        &self,
+        context: &PossiblyCurrentContext<C>,
+        rects: &[Rect],
+    ) -> Result<()> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_partial_update") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_partial_update is not supported").into());
+        }
+
+        context.inner.bind_api();
+
+        let rects = self.clamp_rects(rects)?;
+        unsafe {
+            if self.display.inner.egl.SetDamageRegionKHR(
+                *self.display.inner.raw,
+                self.raw,
+                rects.as_ptr() as *mut _,
+                rects.len() as _,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Clamp the `rects` to the surface size, dropping the ones outside of
+    /// it, and fail when all of them were dropped.
+    fn clamp_rects(&self, rects: &[Rect]) -> Result<Vec<Rect>> {
+        if rects.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (width, height) = unsafe {
+            (self.raw_attribute(egl::WIDTH as EGLint), self.raw_attribute(egl::HEIGHT as EGLint))
+        };
+
+        let rects = rects
+            .iter()
+            .filter_map(|rect| {
+                let x = rect.x.clamp(0, width);
+                let y = rect.y.clamp(0, height);
+                let right = rect.x.saturating_add(rect.width).clamp(0, width);
+                let top = rect.y.saturating_add(rect.height).clamp(0, height);
+                (right > x && top > y).then(|| Rect::new(x, y, right - x, top - y))
+            })
+            .collect::<Vec<_>>();
+
+        if rects.is_empty() {
+            let message = String::from("all the damage rects are outside of the surface");
+            return Err(Error::new(None, Some(message), ErrorKind::BadParameter));
+        }
+
+        Ok(rects)
+    }
+
+    /// Whether the surface is using the sRGB colorspace.
+    ///
+    /// This function returns `false` when `EGL_KHR_gl_colorspace` is not
+    /// supported.
+    pub fn is_srgb(&self) -> bool {
+        self.config.srgb_capable()
+            && unsafe {
+                self.raw_attribute(egl::GL_COLORSPACE as EGLint)
+                    == egl::GL_COLORSPACE_SRGB as EGLint
+            }
+    }
+
+    /// Whether the surface could be made current with the `context`.
+    ///
+    /// The single context could be made current with different surfaces
+    /// sequentially, as long as they were created on the same display with
+    /// the config compatible with the one used by the context and renderable
+    /// with its Api.
+    pub fn is_compatible_with<C>(&self, context: &PossiblyCurrentContext<C>) -> bool {
+        context.inner.is_compatible_with(&self.config)
+    }
+
+    /// The colorspace of the surface.
+    ///
+    /// This function returns [`ColorSpace::Linear`] when
+    /// `EGL_KHR_gl_colorspace` is not supported.
+    pub fn colorspace(&self) -> ColorSpace {
+        if !self.config.srgb_capable() {
+            return ColorSpace::Linear;
+        }
+
+        match unsafe { self.raw_attribute(egl::GL_COLORSPACE as EGLint) } as EGLenum {
+            egl::GL_COLORSPACE_SRGB => ColorSpace::Srgb,
+            egl::GL_COLORSPACE_SCRGB_EXT => ColorSpace::ScRgb,
+            egl::GL_COLORSPACE_SCRGB_LINEAR_EXT => ColorSpace::ScRgbLinear,
+            egl::GL_COLORSPACE_BT2020_LINEAR_EXT => ColorSpace::Bt2020Linear,
+            egl::GL_COLORSPACE_BT2020_PQ_EXT => ColorSpace::Bt2020Pq,
+            _ => ColorSpace::Linear,
+        }
+    }
+
+    /// Set the behavior of the color buffer contents after
+    /// [`Self::swap_buffers`]. See the docs of [`SwapBehavior`].
+    ///
+    /// Preserving the contents requires the config to advertise
+    /// `EGL_SWAP_BEHAVIOR_PRESERVED_BIT`, otherwise
+    /// [`ErrorKind::NotSupported`] is returned.
+    pub fn set_swap_behavior(&self, behavior: SwapBehavior) -> Result<()> {
+        let behavior = match behavior {
+            SwapBehavior::Preserved => {
+                let surface_type =
+                    unsafe { self.config.raw_attribute(egl::SURFACE_TYPE as EGLint) };
+                if surface_type & egl::SWAP_BEHAVIOR_PRESERVED_BIT as EGLint == 0 {
+                    return Err(ErrorKind::NotSupported(
+                        "the config doesn't support preserved swap behavior",
+                    )
+                    .into());
+                }
+
+                egl::BUFFER_PRESERVED
+            },
+            SwapBehavior::Destroyed => egl::BUFFER_DESTROYED,
+        };
+
+        unsafe {
+            if self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::SWAP_BEHAVIOR as EGLint,
+                behavior as EGLint,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// The current behavior of the color buffer contents after
+    /// [`Self::swap_buffers`].
+    pub fn swap_behavior(&self) -> SwapBehavior {
+        let behavior = unsafe { self.raw_attribute(egl::SWAP_BEHAVIOR as EGLint) };
+        if behavior == egl::BUFFER_PRESERVED as EGLint {
+            SwapBehavior::Preserved
+        } else {
+            SwapBehavior::Destroyed
+        }
+    }
+
+    /// Set the filter used to resolve the multisample buffer. See the docs of
+    /// [`MultisampleResolve`].
+    ///
+    /// The box filter requires the config to advertise
+    /// `EGL_MULTISAMPLE_RESOLVE_BOX_BIT`, otherwise
+    /// [`ErrorKind::NotSupported`] is returned.
+    pub fn set_multisample_resolve(&self, resolve: MultisampleResolve) -> Result<()> {
+        let resolve = match resolve {
+            MultisampleResolve::Box => {
+                let surface_type =
+                    unsafe { self.config.raw_attribute(egl::SURFACE_TYPE as EGLint) };
+                if surface_type & egl::MULTISAMPLE_RESOLVE_BOX_BIT as EGLint == 0 {
+                    return Err(ErrorKind::NotSupported(
+                        "the config doesn't support box filtered multisample resolve",
+                    )
+                    .into());
+                }
+
+                egl::MULTISAMPLE_RESOLVE_BOX
+            },
+            MultisampleResolve::Default => egl::MULTISAMPLE_RESOLVE_DEFAULT,
+        };
+
+        unsafe {
+            if self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::MULTISAMPLE_RESOLVE as EGLint,
+                resolve as EGLint,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// The current filter used to resolve the multisample buffer.
+    pub fn multisample_resolve(&self) -> MultisampleResolve {
+        let resolve = unsafe { self.raw_attribute(egl::MULTISAMPLE_RESOLVE as EGLint) };
+        if resolve == egl::MULTISAMPLE_RESOLVE_BOX as EGLint {
+            MultisampleResolve::Box
+        } else {
+            MultisampleResolve::Default
+        }
+    }
+
+    /// The render buffer requested for the surface with `EGL_RENDER_BUFFER`.
+    ///
+    /// The driver may silently render into the back buffer when the single
+    /// buffer was requested, use [`PossiblyCurrentContext::render_buffer`]
+    /// to get the buffer actually used.
+    pub fn render_buffer(&self) -> RenderBuffer {
+        RenderBuffer::from_raw(unsafe { self.raw_attribute(egl::RENDER_BUFFER as EGLint) })
+    }
+
+    /// Attach the `label` to the surface, which is reported by the debug tools
+    /// and passed to the callback registered with
+    /// [`Display::enable_debug_callback`].
+    ///
+    /// This requires `EGL_KHR_debug`.
+    pub fn set_label(&self, label: &str) -> Result<()> {
+        super::debug::label_object(
+            &self.display,
+            egl::OBJECT_SURFACE_KHR,
+            self.raw,
+            &self.label,
+            label,
+        )
+    }
+
+    /// The size of the surface in pixels as reported by EGL with
+    /// `EGL_WIDTH` and `EGL_HEIGHT`.
+    ///
+    /// Unlike the size from the windowing system, which could lag behind, this
+    /// is the size of the buffers EGL renders into.
+    pub fn size(&self) -> Result<(u32, u32)> {
+        let width = self.query_attribute(egl::WIDTH as EGLint)?;
+        let height = self.query_attribute(egl::HEIGHT as EGLint)?;
+        Ok((width as u32, height as u32))
+    }
+
+    /// The horizontal dot pitch of the display the surface is on, in meters.
+    ///
+    /// `None` is returned when the value is unknown, which is the case for
+    /// non window surfaces.
+    pub fn horizontal_resolution(&self) -> Option<f32> {
+        self.scaled_attribute(egl::HORIZONTAL_RESOLUTION as EGLint)
+    }
+
+    /// The vertical dot pitch of the display the surface is on, in meters.
+    ///
+    /// See [`Self::horizontal_resolution`] for details.
+    pub fn vertical_resolution(&self) -> Option<f32> {
+        self.scaled_attribute(egl::VERTICAL_RESOLUTION as EGLint)
+    }
+
+    /// The ratio of the pixel width to its height on the display the surface
+    /// is on.
+    ///
+    /// See [`Self::horizontal_resolution`] for details.
+    pub fn pixel_aspect_ratio(&self) -> Option<f32> {
+        self.scaled_attribute(egl::PIXEL_ASPECT_RATIO as EGLint)
+    }
+
+    /// Query the attribute scaled by `EGL_DISPLAY_SCALING`.
+    fn scaled_attribute(&self, attr: EGLint) -> Option<f32> {
+        match self.query_attribute(attr) {
+            Ok(value) if value != egl::UNKNOWN => Some(value as f32 / egl::DISPLAY_SCALING as f32),
+            _ => None,
+        }
+    }
+
+    /// Query the attribute reporting the error.
+    fn query_attribute(&self, attr: EGLint) -> Result<EGLint> {
+        unsafe {
+            let mut value = 0;
+            if self.display.inner.egl.QuerySurface(
+                *self.display.inner.raw,
+                self.raw,
+                attr,
+                &mut value,
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            Ok(value)
+        }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -321,6 +842,463 @@ impl<T: SurfaceTypeTrait> Surface<T> {
     }
 }
 
+impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Lock the surface with `eglLockSurfaceKHR` to access its pixels from the
+    /// CPU, the surface is unlocked when the returned [`LockedSurface`] is
+    /// dropped.
+    ///
+    /// When `preserve_pixels` is `true` the mapped buffer contains the
+    /// current contents of the surface, otherwise its contents are undefined.
+    /// The surface can't be used for rendering while it's locked, and the
+    /// config must have the `EGL_LOCK_SURFACE_BIT_KHR` surface type.
+    ///
+    /// This requires `EGL_KHR_lock_surface3`. The older `EGL_KHR_lock_surface`
+    /// and `EGL_KHR_lock_surface2` are only used on 32-bit targets, since they
+    /// query the pointer as `EGLint`.
+    pub fn lock(&self, preserve_pixels: bool) -> Result<LockedSurface<'_, T>> {
+        let extensions = &self.display.inner.display_extensions;
+        let has_lock_surface3 = extensions.contains("EGL_KHR_lock_surface3");
+        let has_lock_surface = extensions.contains("EGL_KHR_lock_surface")
+            || extensions.contains("EGL_KHR_lock_surface2");
+        if !has_lock_surface3 && !(has_lock_surface && mem::size_of::<usize>() == 4) {
+            return Err(ErrorKind::NotSupported("EGL_KHR_lock_surface3 is not supported").into());
+        }
+
+        let surface_type = unsafe { self.config.raw_attribute(egl::SURFACE_TYPE as EGLint) };
+        if surface_type & egl::LOCK_SURFACE_BIT_KHR as EGLint == 0 {
+            return Err(ErrorKind::NotSupported("the config doesn't support locking").into());
+        }
+
+        let lock_surface = self.load_lock_surface_fn(b"eglLockSurfaceKHR\0")?;
+        let preserve_pixels = if preserve_pixels { egl::TRUE } else { egl::FALSE };
+        let attrs = [
+            egl::MAP_PRESERVE_PIXELS_KHR as EGLint,
+            preserve_pixels as EGLint,
+            egl::LOCK_USAGE_HINT_KHR as EGLint,
+            (egl::READ_SURFACE_BIT_KHR | egl::WRITE_SURFACE_BIT_KHR) as EGLint,
+            egl::NONE as EGLint,
+        ];
+
+        unsafe {
+            let lock_surface: EglLockSurfaceKhr = mem::transmute(lock_surface);
+            if lock_surface(*self.display.inner.raw, self.raw, attrs.as_ptr()) == egl::FALSE {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        // Unlock the surface on the errors below.
+        let mut locked = LockedSurface {
+            surface: self,
+            pointer: ptr::null_mut(),
+            pitch: 0,
+            origin: BitmapOrigin::LowerLeft,
+        };
+
+        let pointer = if has_lock_surface3 {
+            let query_surface = self.load_lock_surface_fn(b"eglQuerySurface64KHR\0")?;
+            let mut pointer = 0;
+            unsafe {
+                let query_surface: EglQuerySurface64Khr = mem::transmute(query_surface);
+                if query_surface(
+                    *self.display.inner.raw,
+                    self.raw,
+                    egl::BITMAP_POINTER_KHR as EGLint,
+                    &mut pointer,
+                ) == egl::FALSE
+                {
+                    return Err(super::check_error().err().unwrap());
+                }
+            }
+            pointer
+        } else {
+            unsafe { self.raw_attribute(egl::BITMAP_POINTER_KHR as EGLint) as EGLAttrib }
+        };
+
+        locked.pointer = pointer as *mut ffi::c_void;
+        locked.pitch = unsafe { self.raw_attribute(egl::BITMAP_PITCH_KHR as EGLint) } as u32;
+        locked.origin =
+            match unsafe { self.raw_attribute(egl::BITMAP_ORIGIN_KHR as EGLint) } as EGLenum {
+                egl::UPPER_LEFT_KHR => BitmapOrigin::UpperLeft,
+                _ => BitmapOrigin::LowerLeft,
+            };
+
+        Ok(locked)
+    }
+
+    fn load_lock_surface_fn(&self, name: &[u8]) -> Result<*const ffi::c_void> {
+        let proc_address = unsafe {
+            self.display.inner.egl.GetProcAddress(name.as_ptr() as *const _) as *const ffi::c_void
+        };
+
+        if proc_address.is_null() {
+            Err(ErrorKind::NotSupported("EGL_KHR_lock_surface is not supported").into())
+        } else {
+            Ok(proc_address)
+        }
+    }
+}
+
+/// The surface locked with [`Surface::lock`], which provides the CPU access
+/// to its pixels.
+#[derive(Debug)]
+pub struct LockedSurface<'a, T: SurfaceTypeTrait> {
+    surface: &'a Surface<T>,
+    pointer: *mut ffi::c_void,
+    pitch: u32,
+    origin: BitmapOrigin,
+}
+
+impl<T: SurfaceTypeTrait> LockedSurface<'_, T> {
+    /// The pointer to the mapped pixels of the surface, which is valid until
+    /// the surface is unlocked.
+    pub fn as_ptr(&self) -> *mut ffi::c_void {
+        self.pointer
+    }
+
+    /// The number of bytes between the rows of the mapped pixels.
+    pub fn pitch(&self) -> u32 {
+        self.pitch
+    }
+
+    /// The position of the first row of the mapped pixels.
+    pub fn origin(&self) -> BitmapOrigin {
+        self.origin
+    }
+}
+
+impl<T: SurfaceTypeTrait> Drop for LockedSurface<'_, T> {
+    fn drop(&mut self) {
+        let display = &self.surface.display;
+        if let Ok(unlock_surface) = self.surface.load_lock_surface_fn(b"eglUnlockSurfaceKHR\0") {
+            unsafe {
+                let unlock_surface: EglUnlockSurfaceKhr = mem::transmute(unlock_surface);
+                unlock_surface(*display.inner.raw, self.surface.raw);
+            }
+        }
+    }
+}
+
+/// The position of the first row of the pixels mapped with [`Surface::lock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapOrigin {
+    /// The first row is the bottom one, like in OpenGL.
+    LowerLeft,
+
+    /// The first row is the top one.
+    UpperLeft,
+}
+
+impl Surface<WindowSurface> {
+    /// The same as [`GlSurface::resize`], but moves the contents of the
+    /// surface by `dx` and `dy` surface local coordinates on the next attach.
+    ///
+    /// The offset is required to keep the contents in place when the window
+    /// is resized from the top or left edge, like with the client side
+    /// decorations.
+    ///
+    /// # Platform specific
+    ///
+    /// - **Wayland:** the offset is passed to `wl_egl_window_resize`;
+    /// - **Other:** no op.
+    pub fn resize_with_offset<C>(
+        &self,
+        _context: &PossiblyCurrentContext<C>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        dx: i32,
+        dy: i32,
+    ) {
+        self.native_window.as_ref().unwrap().resize(width, height, dx, dy)
+    }
+
+    /// Recreate the surface with the same config and attributes it was
+    /// created with, using its current size.
+    ///
+    /// The surface is destroyed before creating the new one, since the native
+    /// window can't have multiple surfaces, thus it's lost on failure.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_window_surface`], the native window
+    /// the surface was created with must still be valid.
+    pub unsafe fn recreate(self) -> Result<Self> {
+        let (display, config) = (self.display.clone(), self.config.clone());
+        let mut attributes = self.attributes.clone();
+
+        // The window could be resized since the surface creation.
+        if let (Some(width), Some(height)) =
+            (self.width().and_then(NonZeroU32::new), self.height().and_then(NonZeroU32::new))
+        {
+            attributes.width = Some(width);
+            attributes.height = Some(height);
+        }
+
+        drop(self);
+        unsafe { display.create_window_surface(&config, &attributes) }
+    }
+}
+
+#[cfg(android_platform)]
+impl Surface<WindowSurface> {
+    /// Enable collecting the frame timestamps for the surface, which are
+    /// queried with [`Self::frame_timestamps`].
+    ///
+    /// This requires `EGL_ANDROID_get_frame_timestamps`.
+    pub fn enable_timestamps(&self, enable: bool) -> Result<()> {
+        self.ensure_frame_timestamps()?;
+        let enable = if enable { egl::TRUE } else { egl::FALSE };
+        unsafe {
+            if self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::TIMESTAMPS_ANDROID as EGLint,
+                enable as EGLint,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// The id of the frame which will be presented with the next
+    /// [`GlSurface::swap_buffers`].
+    ///
+    /// This requires `EGL_ANDROID_get_frame_timestamps`.
+    pub fn next_frame_id(&self) -> Result<u64> {
+        let get_next_frame_id = self.load_frame_timestamps_fn(b"eglGetNextFrameIdANDROID\0")?;
+        let mut frame_id = 0;
+        unsafe {
+            let get_next_frame_id: EglGetNextFrameIdAndroid = mem::transmute(get_next_frame_id);
+            if get_next_frame_id(*self.display.inner.raw, self.raw, &mut frame_id) == egl::FALSE {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        Ok(frame_id)
+    }
+
+    /// Query the timestamps of the frame with the `frame_id` obtained with
+    /// [`Self::next_frame_id`].
+    ///
+    /// The timestamps must be enabled with [`Self::enable_timestamps`]
+    /// before the frame was presented. The timestamps not supported by the
+    /// implementation are reported as [`FrameTimestamp::Invalid`].
+    ///
+    /// This requires `EGL_ANDROID_get_frame_timestamps`.
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps> {
+        let is_supported =
+            self.load_frame_timestamps_fn(b"eglGetFrameTimestampSupportedANDROID\0")?;
+        let get_frame_timestamps =
+            self.load_frame_timestamps_fn(b"eglGetFrameTimestampsANDROID\0")?;
+
+        let names = [
+            egl::REQUESTED_PRESENT_TIME_ANDROID,
+            egl::RENDERING_COMPLETE_TIME_ANDROID,
+            egl::COMPOSITION_LATCH_TIME_ANDROID,
+            egl::FIRST_COMPOSITION_START_TIME_ANDROID,
+            egl::LAST_COMPOSITION_START_TIME_ANDROID,
+            egl::FIRST_COMPOSITION_GPU_FINISHED_TIME_ANDROID,
+            egl::DISPLAY_PRESENT_TIME_ANDROID,
+            egl::DEQUEUE_READY_TIME_ANDROID,
+            egl::READS_DONE_TIME_ANDROID,
+        ];
+
+        let mut values = [egl::TIMESTAMP_INVALID_ANDROID; 9];
+        unsafe {
+            let is_supported: EglGetFrameTimestampSupportedAndroid = mem::transmute(is_supported);
+            let get_frame_timestamps: EglGetFrameTimestampsAndroid =
+                mem::transmute(get_frame_timestamps);
+
+            // Querying the unsupported timestamp fails, so query only the
+            // supported ones.
+            let (supported_names, supported_index): (Vec<EGLint>, Vec<usize>) = names
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| {
+                    is_supported(*self.display.inner.raw, self.raw, **name as EGLint) != egl::FALSE
+                })
+                .map(|(index, name)| (*name as EGLint, index))
+                .unzip();
+
+            let mut supported_values = vec![0; supported_names.len()];
+            if get_frame_timestamps(
+                *self.display.inner.raw,
+                self.raw,
+                frame_id,
+                supported_names.len() as EGLint,
+                supported_names.as_ptr(),
+                supported_values.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+
+            for (index, value) in supported_index.into_iter().zip(supported_values) {
+                values[index] = value;
+            }
+        }
+
+        let values = values.map(FrameTimestamp::from_raw);
+        Ok(FrameTimestamps {
+            requested_present_time: values[0],
+            rendering_complete_time: values[1],
+            composition_latch_time: values[2],
+            first_composition_start_time: values[3],
+            last_composition_start_time: values[4],
+            first_composition_gpu_finished_time: values[5],
+            display_present_time: values[6],
+            dequeue_ready_time: values[7],
+            reads_done_time: values[8],
+        })
+    }
+
+    /// Query the timing of the compositor, which is used to schedule the
+    /// frames to meet the composition deadline.
+    ///
+    /// This requires `EGL_ANDROID_get_frame_timestamps`.
+    pub fn compositor_timing(&self) -> Result<CompositorTiming> {
+        let get_compositor_timing =
+            self.load_frame_timestamps_fn(b"eglGetCompositorTimingANDROID\0")?;
+
+        let names = [
+            egl::COMPOSITE_DEADLINE_ANDROID as EGLint,
+            egl::COMPOSITE_INTERVAL_ANDROID as EGLint,
+            egl::COMPOSITE_TO_PRESENT_LATENCY_ANDROID as EGLint,
+        ];
+        let mut values = [0; 3];
+        unsafe {
+            let get_compositor_timing: EglGetCompositorTimingAndroid =
+                mem::transmute(get_compositor_timing);
+            if get_compositor_timing(
+                *self.display.inner.raw,
+                self.raw,
+                names.len() as EGLint,
+                names.as_ptr(),
+                values.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap());
+            }
+        }
+
+        Ok(CompositorTiming {
+            composite_deadline: values[0],
+            composite_interval: values[1],
+            composite_to_present_latency: values[2],
+        })
+    }
+
+    fn ensure_frame_timestamps(&self) -> Result<()> {
+        if self.display.inner.display_extensions.contains("EGL_ANDROID_get_frame_timestamps") {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL_ANDROID_get_frame_timestamps is not supported").into())
+        }
+    }
+
+    fn load_frame_timestamps_fn(&self, name: &[u8]) -> Result<*const ffi::c_void> {
+        self.ensure_frame_timestamps()?;
+        let proc_address = unsafe {
+            self.display.inner.egl.GetProcAddress(name.as_ptr() as *const _) as *const ffi::c_void
+        };
+
+        if proc_address.is_null() {
+            Err(ErrorKind::NotSupported("EGL_ANDROID_get_frame_timestamps is not supported").into())
+        } else {
+            Ok(proc_address)
+        }
+    }
+}
+
+impl Surface<PixmapSurface> {
+    /// Recreate the surface with the same config and attributes it was
+    /// created with.
+    ///
+    /// See [`Surface::<WindowSurface>::recreate`] for details.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_pixmap_surface`].
+    pub unsafe fn recreate(self) -> Result<Self> {
+        let (display, config) = (self.display.clone(), self.config.clone());
+        let attributes = self.attributes.clone();
+        drop(self);
+        unsafe { display.create_pixmap_surface(&config, &attributes) }
+    }
+}
+
+impl Surface<PbufferSurface> {
+    /// Recreate the surface with the same config and attributes it was
+    /// created with.
+    ///
+    /// See [`Surface::<WindowSurface>::recreate`] for details.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::create_pbuffer_surface`].
+    pub unsafe fn recreate(self) -> Result<Self> {
+        let (display, config) = (self.display.clone(), self.config.clone());
+        let attributes = self.attributes.clone();
+        drop(self);
+        unsafe { display.create_pbuffer_surface(&config, &attributes) }
+    }
+
+    /// Bind the color buffer of the pbuffer to the texture currently bound to
+    /// `GL_TEXTURE_2D` on the `context`.
+    ///
+    /// The pbuffer must be created with
+    /// [`SurfaceAttributesBuilder::with_texture_format`] and must not be
+    /// current while bound.
+    ///
+    /// [`SurfaceAttributesBuilder::with_texture_format`]: crate::surface::SurfaceAttributesBuilder::with_texture_format
+    pub fn bind_tex_image<C>(&self, context: &PossiblyCurrentContext<C>) -> Result<()> {
+        self.ensure_texture_format()?;
+        context.inner.bind_api();
+        unsafe {
+            if self.display.inner.egl.BindTexImage(
+                *self.display.inner.raw,
+                self.raw,
+                egl::BACK_BUFFER as EGLint,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Release the color buffer of the pbuffer previously bound with
+    /// [`Self::bind_tex_image`].
+    pub fn release_tex_image<C>(&self, context: &PossiblyCurrentContext<C>) -> Result<()> {
+        self.ensure_texture_format()?;
+        context.inner.bind_api();
+        unsafe {
+            if self.display.inner.egl.ReleaseTexImage(
+                *self.display.inner.raw,
+                self.raw,
+                egl::BACK_BUFFER as EGLint,
+            ) == egl::FALSE
+            {
+                super::check_error()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn ensure_texture_format(&self) -> Result<()> {
+        if unsafe { self.raw_attribute(egl::TEXTURE_FORMAT as EGLint) } == egl::NO_TEXTURE as EGLint
+        {
+            Err(ErrorKind::NotSupported("the pbuffer is not bindable to the texture").into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
         unsafe {
@@ -334,10 +1312,10 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     type SurfaceType = T;
 
     fn buffer_age(&self) -> u32 {
-        self.display
-            .inner
-            .display_extensions
-            .contains("EGL_EXT_buffer_age")
+        // `EGL_BUFFER_AGE_KHR` from `EGL_KHR_partial_update` shares the value with
+        // `EGL_BUFFER_AGE_EXT`.
+        let extensions = &self.display.inner.display_extensions;
+        (extensions.contains("EGL_EXT_buffer_age") || extensions.contains("EGL_KHR_partial_update"))
             .then(|| unsafe { self.raw_attribute(egl::BUFFER_AGE_EXT as EGLint) })
             .unwrap_or(0) as u32
     }
@@ -359,7 +1337,7 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
             context.inner.bind_api();
 
             if self.display.inner.egl.SwapBuffers(*self.display.inner.raw, self.raw) == egl::FALSE {
-                super::check_error()
+                check_swap_error()
             } else {
                 Ok(())
             }
@@ -374,6 +1352,14 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
                 SwapInterval::DontWait => 0,
                 SwapInterval::Wait(interval) => interval.get() as EGLint,
             };
+
+            if !self.config.swap_interval_range().contains(&interval) {
+                return Err(ErrorKind::NotSupported(
+                    "swap interval is out of the config's supported range",
+                )
+                .into());
+            }
+
             if self.display.inner.egl.SwapInterval(*self.display.inner.raw, interval) == egl::FALSE
             {
                 super::check_error()
@@ -402,7 +1388,7 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn resize(&self, _context: &Self::Context, width: NonZeroU32, height: NonZeroU32) {
-        self.native_window.as_ref().unwrap().resize(width, height)
+        self.native_window.as_ref().unwrap().resize(width, height, 0, 0)
     }
 }
 
@@ -442,6 +1428,136 @@ impl<T: SurfaceTypeTrait> fmt::Debug for Surface<T> {
 
 impl<T: SurfaceTypeTrait> Sealed for Surface<T> {}
 
+/// Check the error after the failed buffer swap.
+///
+/// The native window which is gone is reported as [`ErrorKind::BadSurface`],
+/// so it could be told apart from [`ErrorKind::ContextLost`]: the former
+/// requires recreating the surface, while the latter the context.
+fn check_swap_error() -> Result<()> {
+    super::check_error().map_err(|err| match err.error_kind() {
+        ErrorKind::BadNativeWindow => {
+            let message = format!("EGL_BAD_NATIVE_WINDOW: {}", ErrorKind::BadSurface.as_str());
+            Error::new(err.raw_code(), Some(message), ErrorKind::BadSurface)
+        },
+        _ => err,
+    })
+}
+
+/// The timestamp of the frame from `EGL_ANDROID_get_frame_timestamps`.
+#[cfg(android_platform)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameTimestamp {
+    /// The event didn't happen yet, the timestamps should be queried later.
+    Pending,
+
+    /// The event won't happen or the timestamp is not supported.
+    Invalid,
+
+    /// The time of the event in nanoseconds of the `CLOCK_MONOTONIC`.
+    Time(i64),
+}
+
+#[cfg(android_platform)]
+impl FrameTimestamp {
+    fn from_raw(value: i64) -> Self {
+        match value {
+            egl::TIMESTAMP_PENDING_ANDROID => Self::Pending,
+            egl::TIMESTAMP_INVALID_ANDROID => Self::Invalid,
+            time => Self::Time(time),
+        }
+    }
+}
+
+/// The timestamps of the presented frame, see
+/// [`Surface::<WindowSurface>::frame_timestamps`].
+#[cfg(android_platform)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTimestamps {
+    /// The presentation time requested by the application.
+    pub requested_present_time: FrameTimestamp,
+
+    /// The time the GPU finished rendering the frame.
+    pub rendering_complete_time: FrameTimestamp,
+
+    /// The time the compositor latched the frame.
+    pub composition_latch_time: FrameTimestamp,
+
+    /// The time the compositor first started compositing the frame.
+    pub first_composition_start_time: FrameTimestamp,
+
+    /// The time the compositor last started compositing the frame.
+    pub last_composition_start_time: FrameTimestamp,
+
+    /// The time the GPU composition of the frame finished.
+    pub first_composition_gpu_finished_time: FrameTimestamp,
+
+    /// The time the frame started to scan out on the display.
+    pub display_present_time: FrameTimestamp,
+
+    /// The time the buffer of the frame became available for reuse.
+    pub dequeue_ready_time: FrameTimestamp,
+
+    /// The time the compositor finished reading the buffer of the frame.
+    pub reads_done_time: FrameTimestamp,
+}
+
+/// The timing of the compositor in nanoseconds, see
+/// [`Surface::<WindowSurface>::compositor_timing`].
+#[cfg(android_platform)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositorTiming {
+    /// The time of the next composition deadline, the frame must be ready
+    /// before it to be presented in the next composition.
+    pub composite_deadline: i64,
+
+    /// The interval between the compositions.
+    pub composite_interval: i64,
+
+    /// The latency from the composition start to the presentation.
+    pub composite_to_present_latency: i64,
+}
+
+/// The color buffer the client API renders into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderBuffer {
+    /// The back buffer, which is presented on the buffer swap.
+    Back,
+
+    /// The single buffer, which is presented as soon as possible.
+    Single,
+}
+
+impl RenderBuffer {
+    pub(crate) fn from_raw(render_buffer: EGLint) -> Self {
+        if render_buffer == egl::SINGLE_BUFFER as EGLint {
+            Self::Single
+        } else {
+            Self::Back
+        }
+    }
+}
+
+/// The behavior of the color buffer contents after swapping the buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapBehavior {
+    /// The contents of the color buffer are preserved, which is useful for
+    /// incremental rendering.
+    Preserved,
+
+    /// The contents of the color buffer become undefined.
+    Destroyed,
+}
+
+/// The filter used to resolve the multisample buffer into the color buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisampleResolve {
+    /// The implementation defined filter, which is the default.
+    Default,
+
+    /// The one-pixel wide box filter, which weights all samples equally.
+    Box,
+}
+
 #[derive(Debug)]
 enum NativeWindow {
     #[cfg(wayland_platform)]
@@ -538,7 +1654,7 @@ impl NativeWindow {
         Ok(native_window)
     }
 
-    fn resize(&self, _width: NonZeroU32, _height: NonZeroU32) {
+    fn resize(&self, _width: NonZeroU32, _height: NonZeroU32, _dx: i32, _dy: i32) {
         #[cfg(wayland_platform)]
         if let Self::Wayland(wl_egl_surface) = self {
             unsafe {
@@ -548,8 +1664,8 @@ impl NativeWindow {
                     *wl_egl_surface as _,
                     _width.get() as _,
                     _height.get() as _,
-                    0,
-                    0
+                    _dx as _,
+                    _dy as _
                 )
             }
         }
@@ -0,0 +1,242 @@
+//! Everything related to `EGLSync` fences.
+
+use std::ffi::c_void;
+#[cfg(unix)]
+use std::os::unix::io::{FromRawFd, OwnedFd};
+use std::time::Duration;
+use std::{fmt, ptr};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLSync, EGLenum, EGLint};
+
+use crate::context::Version;
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+
+impl Display {
+    /// Create a fence sync object, which is signaled once all the commands
+    /// issued before its creation on the current context are complete.
+    ///
+    /// When `native` is `true` the fence is backed by the native fence, which
+    /// could be exported with [`Sync::export_native_fence_fd`]. This requires
+    /// `EGL_ANDROID_native_fence_sync`.
+    ///
+    /// The fence is inserted into the command stream of the current context,
+    /// so the context must be current on the calling thread.
+    pub fn create_fence_sync(&self, native: bool) -> Result<Sync> {
+        let ty = if native {
+            if !self.inner.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+                return Err(ErrorKind::NotSupported(
+                    "EGL_ANDROID_native_fence_sync is not supported",
+                )
+                .into());
+            }
+
+            egl::SYNC_NATIVE_FENCE_ANDROID
+        } else {
+            egl::SYNC_FENCE
+        };
+
+        let sync = unsafe {
+            if self.inner.version >= Version::new(1, 5) {
+                self.inner.egl.CreateSync(*self.inner.raw, ty, ptr::null())
+            } else if self.inner.display_extensions.contains("EGL_KHR_fence_sync") {
+                self.inner.egl.CreateSyncKHR(*self.inner.raw, ty, ptr::null())
+            } else {
+                return Err(ErrorKind::NotSupported("EGL_KHR_fence_sync is not supported").into());
+            }
+        };
+
+        if sync == egl::NO_SYNC {
+            return Err(super::check_error().err().unwrap());
+        }
+
+        Ok(Sync { display: self.clone(), raw: sync })
+    }
+}
+
+/// A wrapper around `EGLSync`.
+///
+/// The sync could be waited on from any context using the same display.
+pub struct Sync {
+    display: Display,
+    raw: EGLSync,
+}
+
+// The `EGLSync` could be accessed from any thread.
+unsafe impl Send for Sync {}
+unsafe impl std::marker::Sync for Sync {}
+
+impl Sync {
+    /// Block the calling thread until the sync is signaled or the `timeout`
+    /// expires.
+    ///
+    /// The `timeout` is passed in nanoseconds, saturating at `EGL_FOREVER`,
+    /// so [`Duration::MAX`] waits forever and [`Duration::ZERO`] only polls
+    /// the sync.
+    ///
+    /// When `flush` is `true` the `EGL_SYNC_FLUSH_COMMANDS_BIT` is passed, so
+    /// the context current on the calling thread is flushed before waiting,
+    /// which prevents waiting forever on the fence which was never submitted.
+    pub fn client_wait(&self, timeout: Duration, flush: bool) -> Result<ClientWaitStatus> {
+        if self.is_signaled()? {
+            return Ok(ClientWaitStatus::Signaled);
+        }
+
+        let flags = if flush { egl::SYNC_FLUSH_COMMANDS_BIT as EGLint } else { 0 };
+        let timeout = timeout.as_nanos().try_into().unwrap_or(egl::FOREVER);
+
+        let result = unsafe {
+            if self.uses_khr() {
+                self.display.inner.egl.ClientWaitSyncKHR(
+                    *self.display.inner.raw,
+                    self.raw,
+                    flags,
+                    timeout,
+                )
+            } else {
+                self.display.inner.egl.ClientWaitSync(
+                    *self.display.inner.raw,
+                    self.raw,
+                    flags,
+                    timeout,
+                )
+            }
+        };
+
+        match result as EGLenum {
+            egl::CONDITION_SATISFIED => Ok(ClientWaitStatus::ConditionSatisfied),
+            egl::TIMEOUT_EXPIRED => Ok(ClientWaitStatus::TimeoutExpired),
+            _ => Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadParameter.into())),
+        }
+    }
+
+    /// Make the context current on the calling thread wait on the GPU until
+    /// the sync is signaled, without blocking the calling thread.
+    ///
+    /// This requires EGL 1.5 or `EGL_KHR_wait_sync`.
+    pub fn wait(&self) -> Result<()> {
+        let result = unsafe {
+            if !self.uses_khr() {
+                self.display.inner.egl.WaitSync(*self.display.inner.raw, self.raw, 0)
+            } else if self.display.inner.display_extensions.contains("EGL_KHR_wait_sync") {
+                self.display.inner.egl.WaitSyncKHR(*self.display.inner.raw, self.raw, 0)
+                    as egl::types::EGLBoolean
+            } else {
+                return Err(ErrorKind::NotSupported("EGL_KHR_wait_sync is not supported").into());
+            }
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check whether the sync is signaled without blocking.
+    pub fn is_signaled(&self) -> Result<bool> {
+        let status = unsafe {
+            if self.uses_khr() {
+                let mut value: EGLint = 0;
+                if self.display.inner.egl.GetSyncAttribKHR(
+                    *self.display.inner.raw,
+                    self.raw,
+                    egl::SYNC_STATUS as EGLint,
+                    &mut value,
+                ) == egl::FALSE
+                {
+                    return Err(super::check_error().err().unwrap());
+                }
+
+                value as EGLenum
+            } else {
+                let mut value: EGLAttrib = 0;
+                if self.display.inner.egl.GetSyncAttrib(
+                    *self.display.inner.raw,
+                    self.raw,
+                    egl::SYNC_STATUS as EGLint,
+                    &mut value,
+                ) == egl::FALSE
+                {
+                    return Err(super::check_error().err().unwrap());
+                }
+
+                value as EGLenum
+            }
+        };
+
+        Ok(status == egl::SIGNALED)
+    }
+
+    /// Export the native fence backing the sync as a file descriptor.
+    ///
+    /// The sync must be created as `native` with
+    /// [`Display::create_fence_sync`] and be flushed before the export.
+    #[cfg(unix)]
+    pub fn export_native_fence_fd(&self) -> Result<OwnedFd> {
+        if !self.display.inner.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+            return Err(
+                ErrorKind::NotSupported("EGL_ANDROID_native_fence_sync is not supported").into()
+            );
+        }
+
+        let fd = unsafe {
+            self.display.inner.egl.DupNativeFenceFDANDROID(*self.display.inner.raw, self.raw)
+        };
+
+        if fd == egl::NO_NATIVE_FENCE_FD_ANDROID {
+            return Err(super::check_error()
+                .err()
+                .unwrap_or_else(|| ErrorKind::BadParameter.into()));
+        }
+
+        // SAFETY: the file descriptor was duplicated, so we own it.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Get a raw handle to the `EGLSync`.
+    pub fn raw_sync(&self) -> *const c_void {
+        self.raw
+    }
+
+    /// Whether the sync must be used with the `EGL_KHR_fence_sync` functions.
+    fn uses_khr(&self) -> bool {
+        self.display.inner.version < Version::new(1, 5)
+    }
+}
+
+/// The result of [`Sync::client_wait`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientWaitStatus {
+    /// The sync was already signaled, so no wait was performed.
+    Signaled,
+
+    /// The `timeout` expired before the sync was signaled.
+    TimeoutExpired,
+
+    /// The sync was signaled while waiting.
+    ConditionSatisfied,
+}
+
+impl Drop for Sync {
+    fn drop(&mut self) {
+        unsafe {
+            if self.uses_khr() {
+                self.display.inner.egl.DestroySyncKHR(*self.display.inner.raw, self.raw);
+            } else {
+                self.display.inner.egl.DestroySync(*self.display.inner.raw, self.raw);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sync")
+            .field("display", &self.display.inner.raw)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
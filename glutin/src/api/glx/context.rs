@@ -29,6 +29,10 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        if context_attributes.protected_content {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let shared_context = if let Some(shared_context) =
             context_attributes.shared_context.as_ref()
         {
@@ -32,6 +32,10 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        if context_attributes.protected_content {
+            return Err(ErrorKind::NotSupported("protected content is not supported").into());
+        }
+
         let hdc = match context_attributes.raw_window_handle.as_ref() {
             handle @ Some(RawWindowHandle::Win32(window)) => unsafe {
                 let _ = config.apply_on_native_window(handle.unwrap());
@@ -83,6 +83,9 @@ pub trait GetGlConfig: Sealed {
 /// Get the raw config.
 pub trait AsRawConfig {
     /// Obtain the [`RawConfig`] of the underlying Api.
+    ///
+    /// The config is only valid while the display it was obtained from is
+    /// alive.
     fn raw_config(&self) -> RawConfig;
 }
 
@@ -108,9 +111,34 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// Whether the color and alpha sizes from [`Self::with_buffer_type`] and
+    /// [`Self::with_alpha_size`] must match exactly instead of being the
+    /// minimum.
+    ///
+    /// This is useful to request formats like `RGB10_A2`, since the larger
+    /// sizes are preferred when matching the minimum, so `8` bits formats could
+    /// be returned first.
+    ///
+    /// By default `false` is requested.
+    ///
+    /// # Api-specific
+    ///
+    /// Only supported with `EGL`.
+    pub fn with_exact_color_sizes(mut self, exact_color_sizes: bool) -> Self {
+        self.template.exact_color_sizes = exact_color_sizes;
+        self
+    }
+
     /// Whether the floating pixel formats should be used.
     ///
     /// By default `false` is requested.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** requires `EGL_EXT_pixel_format_float`, otherwise the config
+    ///   lookup fails with [`ErrorKind::NotSupported`].
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     #[inline]
     pub fn with_float_pixels(mut self, float_pixels: bool) -> Self {
         self.template.float_pixels = float_pixels;
@@ -230,6 +258,46 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// Whether the configs conformant to the requested Api should be returned
+    /// first.
+    ///
+    /// By default the order of the platform is used.
+    ///
+    /// # Api-specific
+    ///
+    /// Only supported with `EGL`.
+    pub fn prefer_conformant(mut self, prefer_conformant: bool) -> Self {
+        self.template.prefer_conformant = prefer_conformant;
+        self
+    }
+
+    /// Whether the configuration should have the transparent color, which
+    /// makes the pixels of that color transparent, like for the color keyed
+    /// overlays.
+    ///
+    /// By default `false` is requested.
+    ///
+    /// # Api-specific
+    ///
+    /// Only supported with `EGL`.
+    pub fn with_transparent_color(mut self, transparent_color: bool) -> Self {
+        self.template.transparent_color = transparent_color;
+        self
+    }
+
+    /// Whether the config must support the native rendering Apis, like X11,
+    /// rendering into the same surfaces as GL.
+    ///
+    /// By default `false` is requested.
+    ///
+    /// # Api-specific
+    ///
+    /// Only supported with `EGL`.
+    pub fn with_native_renderable(mut self, native_renderable: bool) -> Self {
+        self.template.native_renderable = native_renderable;
+        self
+    }
+
     /// Request config that can render to a particular native window.
     ///
     /// # Platform-specific
@@ -279,6 +347,9 @@ pub struct ConfigTemplate {
     /// Bits of alpha in the color buffer.
     pub(crate) alpha_size: u8,
 
+    /// The color and alpha sizes must match exactly.
+    pub(crate) exact_color_sizes: bool,
+
     /// Bits of depth in the depth buffer.
     pub(crate) depth_size: u8,
 
@@ -321,6 +392,15 @@ pub struct ConfigTemplate {
     /// The maximum height of the pbuffer.
     pub(crate) max_pbuffer_height: Option<u32>,
 
+    /// The configs conformant to the requested Api should be returned first.
+    pub(crate) prefer_conformant: bool,
+
+    /// The config should have the transparent color.
+    pub(crate) transparent_color: bool,
+
+    /// The config should support the native rendering.
+    pub(crate) native_renderable: bool,
+
     /// The native window config should support rendering into.
     pub(crate) native_window: Option<RawWindowHandle>,
 }
@@ -332,6 +412,8 @@ impl Default for ConfigTemplate {
 
             alpha_size: 8,
 
+            exact_color_sizes: false,
+
             depth_size: 24,
 
             stencil_size: 8,
@@ -357,6 +439,9 @@ impl Default for ConfigTemplate {
 
             native_window: None,
             hardware_accelerated: None,
+            prefer_conformant: false,
+            transparent_color: false,
+            native_renderable: false,
 
             api: None,
         }
@@ -529,6 +614,44 @@ pub enum RawConfig {
     Cgl(*const std::ffi::c_void),
 }
 
+impl RawConfig {
+    /// The raw `EGLConfig`, if the config is backed by EGL.
+    #[cfg(egl_backend)]
+    pub fn as_egl(&self) -> Option<*const std::ffi::c_void> {
+        match self {
+            Self::Egl(config) => Some(*config),
+            _ => None,
+        }
+    }
+
+    /// The raw `GLXFBConfig`, if the config is backed by GLX.
+    #[cfg(glx_backend)]
+    pub fn as_glx(&self) -> Option<*const std::ffi::c_void> {
+        match self {
+            Self::Glx(config) => Some(*config),
+            _ => None,
+        }
+    }
+
+    /// The pixel format index, if the config is backed by WGL.
+    #[cfg(wgl_backend)]
+    pub fn as_wgl(&self) -> Option<i32> {
+        match self {
+            Self::Wgl(config) => Some(*config),
+            _ => None,
+        }
+    }
+
+    /// The pointer to NSOpenGLPixelFormat, if the config is backed by CGL.
+    #[cfg(cgl_backend)]
+    pub fn as_cgl(&self) -> Option<*const std::ffi::c_void> {
+        match self {
+            Self::Cgl(config) => Some(*config),
+            _ => None,
+        }
+    }
+}
+
 impl AsRawConfig for Config {
     fn raw_config(&self) -> RawConfig {
         gl_api_dispatch!(self; Self(config) => config.raw_config())
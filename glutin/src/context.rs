@@ -7,7 +7,7 @@ use raw_window_handle::RawWindowHandle;
 
 use crate::config::{Config, GetGlConfig};
 use crate::display::{Display, GetGlDisplay};
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
 use crate::surface::{GlSurface, Surface, SurfaceTypeTrait};
 
@@ -33,6 +33,12 @@ pub trait GlContext: Sealed {
     /// Get the [`ContextApi`] used by the context.
     ///
     /// The returned value's [`Version`] will always be `None`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** the Api is queried with `EGL_CONTEXT_CLIENT_TYPE`, use
+    ///   `PossiblyCurrentContext::context_version` from the EGL Api to get the
+    ///   version.
     fn context_api(&self) -> ContextApi;
 }
 
@@ -99,6 +105,10 @@ pub trait PossiblyCurrentGlContext: Sealed {
 
     /// Make [`Self::Surface`] current on the calling thread.
     ///
+    /// The context could be made current with different surfaces one after
+    /// another, like when rendering into multiple windows, given that their
+    /// configs are compatible with the one used to create the context.
+    ///
     /// # Platform specific
     ///
     /// - **macOS: this will block if your main thread is blocked.**
@@ -138,7 +148,8 @@ impl ContextAttributesBuilder {
     /// Sets the *debug* flag for the OpenGL context.
     ///
     /// Debug contexts are usually slower, but give better error reporting.
-    /// This option is ignored when using [`Robustness::NoError`].
+    /// This option is ignored when using [`Robustness::NoError`], use
+    /// [`Self::try_build`] to reject such combination instead.
     ///
     /// The default value for this flag is `false`.
     pub fn with_debug(mut self, debug: bool) -> Self {
@@ -146,6 +157,23 @@ impl ContextAttributesBuilder {
         self
     }
 
+    /// Sets the *forward compatible* flag for the OpenGL context.
+    ///
+    /// Forward compatible contexts don't expose the functionality deprecated
+    /// by the requested version, which helps to catch its use during
+    /// development. The flag only applies to OpenGL contexts and is ignored
+    /// for OpenGL ES.
+    ///
+    /// The default value for this flag is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX/WGL/CGL:** the flag is ignored.
+    pub fn with_forward_compatible(mut self, forward_compatible: bool) -> Self {
+        self.attributes.forward_compatible = forward_compatible;
+        self
+    }
+
     /// Share the display lists with the given context.
     ///
     /// To get sharing working it's recommended to use the same [`Config`] when
@@ -154,8 +182,11 @@ impl ContextAttributesBuilder {
     /// # Platform-specific
     ///
     /// - **Wayland:** both contexts must use the same Wayland connection.
+    /// - **EGL:** both contexts must be created on the same display, otherwise
+    ///   context creation fails with [`ErrorKind::NotSupported`].
     ///
     /// [`Config`]: crate::config::Config
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     pub fn with_sharing(mut self, context: &impl AsRawContext) -> Self {
         self.attributes.shared_context = Some(context.raw_context());
         self
@@ -181,6 +212,21 @@ impl ContextAttributesBuilder {
         self
     }
 
+    /// Set the priority hint for the context. See the docs of
+    /// [`ContextPriority`].
+    ///
+    /// The priority is only a hint and the driver may grant a lower one. By
+    /// default the priority is not specified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** requires `EGL_IMG_context_priority`.
+    /// - **GLX/WGL/CGL:** the priority is ignored.
+    pub fn with_priority(mut self, priority: ContextPriority) -> Self {
+        self.attributes.priority = Some(priority);
+        self
+    }
+
     /// Set the desired OpenGL context profile. See the docs of [`GlProfile`].
     ///
     /// By default the profile is unspecified.
@@ -201,6 +247,38 @@ impl ContextAttributesBuilder {
         self
     }
 
+    /// Request the protected context, which could render the protected
+    /// content, like for the DRM video playback.
+    ///
+    /// By default the context is not protected.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** requires `EGL_EXT_protected_content`, otherwise the context
+    ///   creation fails with [`ErrorKind::NotSupported`].
+    /// - **GLX/WGL/CGL:** not supported, the context creation fails with
+    ///   [`ErrorKind::NotSupported`].
+    pub fn with_protected_content(mut self, protected_content: bool) -> Self {
+        self.attributes.protected_content = protected_content;
+        self
+    }
+
+    /// Append the attributes to the ones used to create the context verbatim,
+    /// which allows using the vendor specific attributes not known to glutin.
+    ///
+    /// The attributes are `(attribute, value)` pairs, and must not repeat the
+    /// attributes set by glutin itself, otherwise the context creation fails
+    /// with [`ErrorKind::BadAttribute`].
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** the attributes are passed to `eglCreateContext`.
+    /// - **GLX/WGL/CGL:** the attributes are ignored.
+    pub fn with_raw_attributes(mut self, attributes: &[(i32, i32)]) -> Self {
+        self.attributes.raw_attributes.extend_from_slice(attributes);
+        self
+    }
+
     /// Build the context attributes.
     ///
     /// The `raw_window_handle` isn't required and here for WGL compatibility.
@@ -213,6 +291,31 @@ impl ContextAttributesBuilder {
         self.attributes.raw_window_handle = raw_window_handle;
         self.attributes
     }
+
+    /// Build the context attributes, validating that the requested options
+    /// could be used together.
+    ///
+    /// Unlike [`Self::build`], which silently ignores the *debug* flag for
+    /// [`Robustness::NoError`] contexts, this returns
+    /// [`ErrorKind::BadAttribute`] describing the conflict.
+    ///
+    /// Note that the support for [`Robustness::NoError`] itself is only known
+    /// when creating the context, which returns [`ErrorKind::NotSupported`]
+    /// when it's missing, like without `EGL_KHR_create_context_no_error`.
+    pub fn try_build(
+        self,
+        raw_window_handle: Option<RawWindowHandle>,
+    ) -> Result<ContextAttributes> {
+        if self.attributes.robustness == Robustness::NoError && self.attributes.debug {
+            return Err(Error::new(
+                None,
+                Some(String::from("no error context can't be a debug context")),
+                ErrorKind::BadAttribute,
+            ));
+        }
+
+        Ok(self.build(raw_window_handle))
+    }
 }
 
 /// The attributes that are used to create a graphics context.
@@ -222,14 +325,22 @@ pub struct ContextAttributes {
 
     pub(crate) debug: bool,
 
+    pub(crate) forward_compatible: bool,
+
     pub(crate) robustness: Robustness,
 
     pub(crate) profile: Option<GlProfile>,
 
+    pub(crate) priority: Option<ContextPriority>,
+
     pub(crate) api: Option<ContextApi>,
 
     pub(crate) shared_context: Option<RawContext>,
 
+    pub(crate) protected_content: bool,
+
+    pub(crate) raw_attributes: Vec<(i32, i32)>,
+
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
 }
 
@@ -247,9 +358,9 @@ pub enum Robustness {
     /// Please know what you're doing before using it. See the
     /// `GL_KHR_no_error` extension.
     ///
-    /// Since this option is purely an optimization, no error will be returned
-    /// if the backend doesn't support it. Instead it will automatically
-    /// fall back to [`Robustness::NotRobust`].
+    /// Creating the context returns [`ErrorKind::NotSupported`] when the
+    /// backend doesn't support it, like when `EGL_KHR_create_context_no_error`
+    /// is missing.
     NoError,
 
     /// Everything is checked to avoid any crash. The driver will attempt to
@@ -263,6 +374,36 @@ pub enum Robustness {
     RobustLoseContextOnReset,
 }
 
+/// The priority hint of the context used by the scheduler of the GPU.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ContextPriority {
+    /// The context yields to the contexts with higher priority.
+    Low,
+
+    /// The default priority of the context.
+    #[default]
+    Medium,
+
+    /// The context is preferred over the contexts with lower priority.
+    High,
+}
+
+/// The graphics reset status of the robust context.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResetStatus {
+    /// No graphics reset happened.
+    NoError,
+
+    /// The graphics reset was caused by the context.
+    GuiltyContextReset,
+
+    /// The graphics reset was caused by some other context.
+    InnocentContextReset,
+
+    /// The graphics reset happened, but its cause is unknown.
+    UnknownContextReset,
+}
+
 /// Describes the requested OpenGL context profiles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GlProfile {
@@ -608,6 +749,44 @@ pub enum RawContext {
     Cgl(*const ffi::c_void),
 }
 
+impl RawContext {
+    /// The raw EGL context, if the context is backed by EGL.
+    #[cfg(egl_backend)]
+    pub fn as_egl(&self) -> Option<*const ffi::c_void> {
+        match self {
+            Self::Egl(context) => Some(*context),
+            _ => None,
+        }
+    }
+
+    /// The raw GLX context, if the context is backed by GLX.
+    #[cfg(glx_backend)]
+    pub fn as_glx(&self) -> Option<*const ffi::c_void> {
+        match self {
+            Self::Glx(context) => Some(*context),
+            _ => None,
+        }
+    }
+
+    /// The HGLRC pointer, if the context is backed by WGL.
+    #[cfg(wgl_backend)]
+    pub fn as_wgl(&self) -> Option<*const ffi::c_void> {
+        match self {
+            Self::Wgl(context) => Some(*context),
+            _ => None,
+        }
+    }
+
+    /// The pointer to NSOpenGLContext, if the context is backed by CGL.
+    #[cfg(cgl_backend)]
+    pub fn as_cgl(&self) -> Option<*const ffi::c_void> {
+        match self {
+            Self::Cgl(context) => Some(*context),
+            _ => None,
+        }
+    }
+}
+
 /// Pick `GlProfile` and `Version` based on the provided params.
 #[cfg(any(egl_backend, glx_backend, wgl_backend))]
 pub(crate) fn pick_profile(
@@ -42,6 +42,10 @@ pub trait GlDisplay: Sealed {
 
     /// Find configurations matching the given `template`.
     ///
+    /// All the matching configs are returned in the order preferred by the
+    /// platform, so custom selection logic could be applied to them, like
+    /// minimizing the depth size. The configs are cheap to clone.
+    ///
     /// # Safety
     ///
     /// Some platforms use [`RawWindowHandle`] to pick configs, so it
@@ -69,8 +73,15 @@ pub trait GlDisplay: Sealed {
     ///   [`GlSurface::swap_buffers`]. To workaround this behavior the current
     ///   context should be made [`not current`].
     ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** when the driver is out of memory [`ErrorKind::OutOfMemory`]
+    ///   is returned, so the creation could be retried later. The Api bound on
+    ///   the calling thread is restored on failure.
+    ///
     /// [`RawWindowHandle`]: raw_window_handle::RawWindowHandle
     /// [`not current`]: crate::context::PossiblyCurrentGlContext::make_not_current
+    /// [`ErrorKind::OutOfMemory`]: crate::error::ErrorKind::OutOfMemory
     unsafe fn create_context(
         &self,
         config: &Self::Config,
@@ -119,6 +130,8 @@ pub trait GlDisplay: Sealed {
     ///
     /// # Api-specific
     ///
+    /// - **EGL:** when `eglGetProcAddress` doesn't return the function, it's
+    ///   looked up in the `libGLESv2` or `libGL` library.
     /// - **WGL:** to load all the functions you must have a current context on
     ///   the calling thread, otherwise only a limited set of functions will be
     ///   loaded.
@@ -160,6 +173,9 @@ pub trait GetDisplayExtensions: Sealed {
 /// Get the raw handle to the [`Display`].
 pub trait AsRawDisplay {
     /// A raw handle to the underlying Api display.
+    ///
+    /// The handle is owned by glutin and is only valid while the display is
+    /// alive, thus it must not be terminated or destroyed by the user.
     fn raw_display(&self) -> RawDisplay;
 }
 
@@ -590,3 +606,23 @@ pub enum RawDisplay {
     #[cfg(cgl_backend)]
     Cgl,
 }
+
+impl RawDisplay {
+    /// The raw `EGLDisplay`, if the display is backed by EGL.
+    #[cfg(egl_backend)]
+    pub fn as_egl(&self) -> Option<*const std::ffi::c_void> {
+        match self {
+            Self::Egl(display) => Some(*display),
+            _ => None,
+        }
+    }
+
+    /// The raw X11 display used by GLX, if the display is backed by GLX.
+    #[cfg(glx_backend)]
+    pub fn as_glx(&self) -> Option<*const std::ffi::c_void> {
+        match self {
+            Self::Glx(display) => Some(*display),
+            _ => None,
+        }
+    }
+}
@@ -41,6 +41,10 @@ impl Error {
     }
 
     /// The underlying raw code in case it's present.
+    ///
+    /// For EGL this is the code returned by `eglGetError`, like
+    /// `EGL_BAD_ALLOC`, which allows handling the errors not distinguished
+    /// by the [`ErrorKind`].
     #[inline]
     pub fn raw_code(&self) -> Option<i64> {
         self.raw_code
@@ -31,6 +31,9 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
     /// buffer is either a new one or we failed to get the information about
     /// its age. In both cases you must redraw the entire buffer.
     ///
+    /// The age is only meaningful when the surface is current as the draw
+    /// surface on the calling thread, otherwise `0` is usually returned.
+    ///
     /// # Platform-specific
     ///
     /// - **Wayland:** this call will latch the underlying back buffer, meaning
@@ -57,6 +60,16 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
 
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered.
+    ///
+    /// # Api-specific.
+    ///
+    /// On EGL the lost context is reported as [`ErrorKind::ContextLost`],
+    /// meaning that the context must be recreated, while the lost native
+    /// window is reported as [`ErrorKind::BadSurface`], meaning that the
+    /// surface must be recreated.
+    ///
+    /// [`ErrorKind::ContextLost`]: crate::error::ErrorKind::ContextLost
+    /// [`ErrorKind::BadSurface`]: crate::error::ErrorKind::BadSurface
     fn swap_buffers(&self, context: &Self::Context) -> Result<()>;
 
     /// Check whether the surface is current on to the current thread.
@@ -73,6 +86,12 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
     /// Set swap interval for the surface.
     ///
     /// See [`crate::surface::SwapInterval`] for details.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** returns [`Err`] when the interval is outside of the
+    ///   `EGL_MIN_SWAP_INTERVAL` and `EGL_MAX_SWAP_INTERVAL` range of the
+    ///   surface's config.
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()>;
 
     /// Resize the surface to a new size.
@@ -122,14 +141,41 @@ impl<T: SurfaceTypeTrait + Default> SurfaceAttributesBuilder<T> {
     /// Specify whether the surface should support srgb or not. Passing `None`
     /// means you don't care.
     ///
+    /// When `Some(true)` is passed and the config is not [`srgb capable`],
+    /// EGL surface creation will fail with [`ErrorKind::NotSupported`]. EGL
+    /// window surface creation also fails with it when the config doesn't
+    /// support window surfaces.
+    ///
     /// # Api-specific.
     ///
     /// This only controls EGL surfaces, other platforms use the context for
     /// that.
+    ///
+    /// [`srgb capable`]: crate::config::GlConfig::srgb_capable
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     pub fn with_srgb(mut self, srgb: Option<bool>) -> Self {
         self.attributes.srgb = srgb;
         self
     }
+
+    /// Request the surface to hold the protected content, which can't be read
+    /// back by the unprotected contexts, like for the DRM video playback.
+    ///
+    /// By default the surface is not protected.
+    ///
+    /// # Api-specific.
+    ///
+    /// - **EGL:** requires `EGL_EXT_protected_surface` or
+    ///   `EGL_EXT_protected_content`, otherwise the surface creation fails
+    ///   with [`ErrorKind::NotSupported`].
+    /// - **GLX/WGL/CGL:** ignored, since the protected context can't be
+    ///   created.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
+    pub fn with_protected_content(mut self, protected_content: bool) -> Self {
+        self.attributes.protected_content = protected_content;
+        self
+    }
 }
 
 impl SurfaceAttributesBuilder<WindowSurface> {
@@ -148,6 +194,22 @@ impl SurfaceAttributesBuilder<WindowSurface> {
         self
     }
 
+    /// Specify the colorspace of the surface, like the HDR ones. Passing
+    /// `None` means that the colorspace is picked with [`Self::with_srgb`].
+    ///
+    /// When the display doesn't support the colorspace, the surface creation
+    /// will fail with [`ErrorKind::NotSupported`].
+    ///
+    /// # Api-specific.
+    ///
+    /// This is EGL specific, other platforms use the context for that.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
+    pub fn with_colorspace(mut self, colorspace: Option<ColorSpace>) -> Self {
+        self.attributes.colorspace = colorspace;
+        self
+    }
+
     /// Build the surface attributes suitable to create a window surface.
     pub fn build(
         mut self,
@@ -164,6 +226,10 @@ impl SurfaceAttributesBuilder<WindowSurface> {
 
 impl SurfaceAttributesBuilder<PbufferSurface> {
     /// Request the largest pbuffer.
+    ///
+    /// When the requested size can't be allocated, the largest available
+    /// pbuffer is created instead of failing. Use [`GlSurface::width`] and
+    /// [`GlSurface::height`] to get the granted size.
     pub fn with_largest_pbuffer(mut self, largest_pbuffer: bool) -> Self {
         self.attributes.largest_pbuffer = largest_pbuffer;
         self
@@ -176,7 +242,45 @@ impl SurfaceAttributesBuilder<PbufferSurface> {
         self
     }
 
+    /// Make the pbuffer bindable to a 2D texture with the given `format`.
+    /// Passing `None` means that the pbuffer can't be bound to a texture.
+    ///
+    /// By default the pbuffer is not bindable.
+    ///
+    /// # Api-specific.
+    ///
+    /// This is EGL specific, the config must support binding to the texture
+    /// with the given format, otherwise the surface creation will fail. The
+    /// pbuffer is bound with `Surface::bind_tex_image` on the EGL surface.
+    pub fn with_texture_format(mut self, texture_format: Option<TextureFormat>) -> Self {
+        self.attributes.texture_format = texture_format;
+        self
+    }
+
+    /// Allocate the mipmap levels for the pbuffer bound to the texture.
+    ///
+    /// By default the mipmap levels are not allocated.
+    ///
+    /// # Api-specific.
+    ///
+    /// This is EGL specific and only has effect along with
+    /// [`Self::with_texture_format`].
+    pub fn with_mipmap_texture(mut self, mipmap_texture: bool) -> Self {
+        self.attributes.mipmap_texture = mipmap_texture;
+        self
+    }
+
     /// Build the surface attributes suitable to create a pbuffer surface.
+    ///
+    /// # Api-specific.
+    ///
+    /// - **EGL:** the surface creation fails with [`ErrorKind::NotSupported`]
+    ///   naming the exceeded limit when the size is larger than the
+    ///   `EGL_MAX_PBUFFER_WIDTH`, `EGL_MAX_PBUFFER_HEIGHT` or
+    ///   `EGL_MAX_PBUFFER_PIXELS` of the config, unless
+    ///   [`Self::with_largest_pbuffer`] is requested.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     pub fn build(
         mut self,
         width: NonZeroU32,
@@ -200,10 +304,14 @@ impl SurfaceAttributesBuilder<PixmapSurface> {
 #[derive(Default, Debug, Clone)]
 pub struct SurfaceAttributes<T: SurfaceTypeTrait> {
     pub(crate) srgb: Option<bool>,
+    pub(crate) colorspace: Option<ColorSpace>,
     pub(crate) single_buffer: bool,
     pub(crate) width: Option<NonZeroU32>,
     pub(crate) height: Option<NonZeroU32>,
     pub(crate) largest_pbuffer: bool,
+    pub(crate) texture_format: Option<TextureFormat>,
+    pub(crate) mipmap_texture: bool,
+    pub(crate) protected_content: bool,
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
     pub(crate) native_pixmap: Option<NativePixmap>,
     _ty: PhantomData<T>,
@@ -247,6 +355,38 @@ impl SurfaceTypeTrait for PixmapSurface {
 
 impl Sealed for PixmapSurface {}
 
+/// The colorspace of the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// The linear colorspace.
+    Linear,
+
+    /// The sRGB colorspace.
+    Srgb,
+
+    /// The scRGB colorspace with the sRGB transfer function.
+    ScRgb,
+
+    /// The scRGB colorspace with the linear transfer function.
+    ScRgbLinear,
+
+    /// The BT.2020 colorspace with the linear transfer function.
+    Bt2020Linear,
+
+    /// The BT.2020 colorspace with the PQ transfer function.
+    Bt2020Pq,
+}
+
+/// The format of the texture the pbuffer is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFormat {
+    /// The texture has the RGB format.
+    Rgb,
+
+    /// The texture has the RGBA format.
+    Rgba,
+}
+
 /// The underlying type of the surface.
 #[derive(Debug, Clone, Copy)]
 pub enum SurfaceType {
@@ -530,7 +670,9 @@ pub enum RawSurface {
 
 /// The rect that is being used in various surface operations.
 ///
-/// The origin is in the bottom left of the surface.
+/// The origin is in the bottom left of the surface, with `y` growing upwards.
+/// Rects obtained from windowing systems with the top left origin must be
+/// flipped with `y = surface_height - y - height`.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Rect {
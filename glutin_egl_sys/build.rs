@@ -29,20 +29,30 @@ fn main() {
             "EGL_EXT_device_enumeration",
             "EGL_EXT_device_query",
             "EGL_EXT_device_query_name",
+            "EGL_EXT_image_dma_buf_import",
+            "EGL_EXT_image_dma_buf_import_modifiers",
             "EGL_EXT_pixel_format_float",
             "EGL_EXT_platform_base",
             "EGL_EXT_platform_device",
             "EGL_EXT_platform_wayland",
             "EGL_EXT_platform_x11",
             "EGL_EXT_swap_buffers_with_damage",
+            "EGL_IMG_context_priority",
             "EGL_KHR_create_context",
             "EGL_KHR_create_context_no_error",
+            "EGL_KHR_debug",
             "EGL_KHR_display_reference",
             "EGL_KHR_fence_sync",
+            "EGL_KHR_gl_texture_2D_image",
+            "EGL_KHR_image_base",
+            "EGL_KHR_partial_update",
             "EGL_KHR_platform_android",
             "EGL_KHR_platform_gbm",
             "EGL_KHR_platform_wayland",
             "EGL_KHR_platform_x11",
+            "EGL_KHR_stream",
+            "EGL_KHR_stream_consumer_gltexture",
+            "EGL_KHR_stream_fifo",
             "EGL_KHR_swap_buffers_with_damage",
             "EGL_KHR_wait_sync",
             "EGL_MESA_platform_gbm",
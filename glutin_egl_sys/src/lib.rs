@@ -30,8 +30,55 @@ pub mod egl {
     // EGL_EXT_platform_xcb
     pub const PLATFORM_XCB_EXT: super::EGLenum = 0x31DC;
     pub const PLATFORM_XCB_SCREEN_EXT: super::EGLenum = 0x31DE;
+    // EGL_MESA_platform_surfaceless
+    pub const PLATFORM_SURFACELESS_MESA: super::EGLenum = 0x31DD;
+    // EGL_ANDROID_image_native_buffer
+    pub const NATIVE_BUFFER_ANDROID: super::EGLenum = 0x3140;
+    // EGL_EXT_gl_colorspace_scrgb
+    pub const GL_COLORSPACE_SCRGB_EXT: super::EGLenum = 0x3351;
+    // EGL_EXT_gl_colorspace_scrgb_linear
+    pub const GL_COLORSPACE_SCRGB_LINEAR_EXT: super::EGLenum = 0x3350;
+    // EGL_EXT_gl_colorspace_bt2020_linear
+    pub const GL_COLORSPACE_BT2020_LINEAR_EXT: super::EGLenum = 0x333F;
+    // EGL_EXT_gl_colorspace_bt2020_pq
+    pub const GL_COLORSPACE_BT2020_PQ_EXT: super::EGLenum = 0x3340;
     // EGL_EXT_device_query_name
     pub const RENDERER_EXT: super::EGLenum = 0x335F;
+    // EGL_EXT_device_drm_render_node
+    pub const DRM_RENDER_NODE_FILE_EXT: super::EGLenum = 0x3377;
+    // EGL_ANDROID_get_frame_timestamps
+    pub const TIMESTAMPS_ANDROID: super::EGLenum = 0x3430;
+    pub const COMPOSITE_DEADLINE_ANDROID: super::EGLenum = 0x3431;
+    pub const COMPOSITE_INTERVAL_ANDROID: super::EGLenum = 0x3432;
+    pub const COMPOSITE_TO_PRESENT_LATENCY_ANDROID: super::EGLenum = 0x3433;
+    pub const REQUESTED_PRESENT_TIME_ANDROID: super::EGLenum = 0x3434;
+    pub const RENDERING_COMPLETE_TIME_ANDROID: super::EGLenum = 0x3435;
+    pub const COMPOSITION_LATCH_TIME_ANDROID: super::EGLenum = 0x3436;
+    pub const FIRST_COMPOSITION_START_TIME_ANDROID: super::EGLenum = 0x3437;
+    pub const LAST_COMPOSITION_START_TIME_ANDROID: super::EGLenum = 0x3438;
+    pub const FIRST_COMPOSITION_GPU_FINISHED_TIME_ANDROID: super::EGLenum = 0x3439;
+    pub const DISPLAY_PRESENT_TIME_ANDROID: super::EGLenum = 0x343A;
+    pub const DEQUEUE_READY_TIME_ANDROID: super::EGLenum = 0x343B;
+    pub const READS_DONE_TIME_ANDROID: super::EGLenum = 0x343C;
+    pub const TIMESTAMP_PENDING_ANDROID: i64 = -2;
+    pub const TIMESTAMP_INVALID_ANDROID: i64 = -1;
+    // EGL_KHR_lock_surface
+    pub const LOCK_SURFACE_BIT_KHR: super::EGLenum = 0x0080;
+    pub const READ_SURFACE_BIT_KHR: super::EGLenum = 0x0001;
+    pub const WRITE_SURFACE_BIT_KHR: super::EGLenum = 0x0002;
+    pub const MAP_PRESERVE_PIXELS_KHR: super::EGLenum = 0x30C4;
+    pub const LOCK_USAGE_HINT_KHR: super::EGLenum = 0x30C5;
+    pub const BITMAP_POINTER_KHR: super::EGLenum = 0x30C6;
+    pub const BITMAP_PITCH_KHR: super::EGLenum = 0x30C7;
+    pub const BITMAP_ORIGIN_KHR: super::EGLenum = 0x30C8;
+    pub const LOWER_LEFT_KHR: super::EGLenum = 0x30CE;
+    pub const UPPER_LEFT_KHR: super::EGLenum = 0x30CF;
+    // EGL_EXT_protected_content
+    pub const PROTECTED_CONTENT_EXT: super::EGLenum = 0x32C0;
+    // EGL_KHR_context_flush_control
+    pub const CONTEXT_RELEASE_BEHAVIOR_KHR: super::EGLenum = 0x2097;
+    pub const CONTEXT_RELEASE_BEHAVIOR_NONE_KHR: super::EGLenum = 0;
+    pub const CONTEXT_RELEASE_BEHAVIOR_FLUSH_KHR: super::EGLenum = 0x2098;
     // EGL_ANGLE_platform_angle - https://chromium.googlesource.com/angle/angle/+/HEAD/extensions/EGL_ANGLE_platform_angle.txt
     pub const PLATFORM_ANGLE_ANGLE: super::EGLenum = 0x3202;
     pub const PLATFORM_ANGLE_TYPE_ANGLE: super::EGLenum = 0x3203;